@@ -0,0 +1,220 @@
+//! Reports the cost of the bounty state transitions.
+//!
+//! First compares decoding and re-encoding a whole `Bounty` with only its
+//! `BountyHeader`, on a locked bounty created by the program running natively.
+//! Then, if the program was built with `cargo build-sbf`, reports the compute
+//! units of each instruction. Run with `cargo run --release --example
+//! compute_units`. To compare against another revision, build it into a
+//! separate directory and point `BPF_OUT_DIR` at it.
+use std::{hint::black_box, path::PathBuf, time::Instant};
+
+use bounty_program::{
+    instruction::{find_bounty_address, BountyInstruction},
+    issue::IssueId,
+    state::{AccountState, Bounty, BountyHeader, BountyStatus, ForgePlatform},
+};
+use solana_program_test::{processor, tokio, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+
+async fn measure(
+    context: &mut ProgramTestContext,
+    label: &str,
+    instruction: Instruction,
+    signer: &Keypair,
+) {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer.pubkey()),
+        &[signer],
+        blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    if let Err(err) = result.result {
+        panic!("{} failed: {:?}", label, err);
+    }
+
+    let units = result.metadata.map_or(0, |metadata| metadata.compute_units_consumed);
+    println!("{:<18} {:>8}", label, units);
+}
+
+fn create_sol_bounty(creator: &Keypair, issue_number: u64, deadline: i64) -> (Instruction, Pubkey) {
+    let program_id = bounty_program::id();
+    let issue_url = format!("https://github.com/solforge/bounties/issues/{}", issue_number);
+    let issue_hash = IssueId::from_issue_url(ForgePlatform::GitHub, &issue_url).unwrap().hash();
+    let (bounty, _) = find_bounty_address(&program_id, &issue_hash, &creator.pubkey());
+    let instruction = BountyInstruction::create_sol_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        LAMPORTS_PER_SOL,
+        "Fix the flaky integration test on CI".to_string(),
+        issue_hash,
+        issue_url,
+        "https://github.com/solforge/bounties".to_string(),
+        deadline,
+        ForgePlatform::GitHub,
+        None,
+    )
+    .unwrap();
+    (instruction, bounty)
+}
+
+/// Funds a creator and a claimant from the test payer
+async fn fund_wallets(context: &mut ProgramTestContext) -> (Keypair, Keypair) {
+    let creator = Keypair::new();
+    let claimant = Keypair::new();
+    let fund = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::transfer(&context.payer.pubkey(), &creator.pubkey(), 10 * LAMPORTS_PER_SOL),
+            system_instruction::transfer(&context.payer.pubkey(), &claimant.pubkey(), 10 * LAMPORTS_PER_SOL),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(fund).await.unwrap();
+    (creator, claimant)
+}
+
+async fn deadline(context: &mut ProgramTestContext) -> i64 {
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp + 7 * 24 * 60 * 60
+}
+
+/// Average nanoseconds of `iterations` runs of `f`
+fn time_per_run(iterations: u32, mut f: impl FnMut()) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    start.elapsed().as_nanos() as f64 / iterations as f64
+}
+
+/// Compares a state transition through the whole bounty and through its header only
+async fn compare_decoding() {
+    let program_id = bounty_program::id();
+    let mut program_test =
+        ProgramTest::new("bounty_program", program_id, processor!(bounty_program::process_instruction));
+    program_test.prefer_bpf(false);
+    let mut context = program_test.start_with_context().await;
+    let (creator, claimant) = fund_wallets(&mut context).await;
+    let deadline = deadline(&mut context).await;
+
+    // A locked bounty, so the account holds every string including the PR URL
+    let (create, bounty) = create_sol_bounty(&creator, 1, deadline);
+    let lock = BountyInstruction::lock_bounty(
+        &program_id,
+        &claimant.pubkey(),
+        &bounty,
+        "https://github.com/solforge/bounties/pull/2".to_string(),
+    )
+    .unwrap();
+    for (instruction, signer) in [(create, &creator), (lock, &claimant)] {
+        let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let transaction =
+            Transaction::new_signed_with_payer(&[instruction], Some(&signer.pubkey()), &[signer], blockhash);
+        context.banks_client.process_transaction(transaction).await.unwrap();
+    }
+    let mut data = context.banks_client.get_account(bounty).await.unwrap().unwrap().data;
+
+    const ITERATIONS: u32 = 100_000;
+    let full = time_per_run(ITERATIONS, || {
+        let mut bounty = Bounty::unpack(black_box(&data)).unwrap();
+        bounty.header.state = BountyStatus::Claimed;
+        bounty.pack(black_box(&mut data)).unwrap();
+    });
+    let header = time_per_run(ITERATIONS, || {
+        let mut header = BountyHeader::unpack(black_box(&data)).unwrap();
+        header.state = BountyStatus::Claimed;
+        header.pack(black_box(&mut data)).unwrap();
+    });
+
+    println!("Decode and encode a {}-byte locked bounty, native:", data.len());
+    println!("{:<18} {:>8}", "path", "ns");
+    println!("{:<18} {:>8.0}", "Bounty", full);
+    println!("{:<18} {:>8.0}", "BountyHeader", header);
+    println!("{:<18} {:>7.1}x", "speedup", full / header);
+}
+
+/// Whether `cargo build-sbf` output is where `ProgramTest` looks for it
+fn program_is_built() -> bool {
+    let mut dirs: Vec<PathBuf> = ["BPF_OUT_DIR", "SBF_OUT_DIR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok().map(PathBuf::from))
+        .collect();
+    dirs.push(PathBuf::from("tests/fixtures"));
+    dirs.extend(std::env::current_dir().ok());
+    dirs.iter().any(|dir| dir.join("bounty_program.so").exists())
+}
+
+#[tokio::main]
+async fn main() {
+    compare_decoding().await;
+
+    if !program_is_built() {
+        println!();
+        println!("bounty_program.so not found, build it with `cargo build-sbf` to report compute units");
+        return;
+    }
+
+    let program_id = bounty_program::id();
+    let mut program_test = ProgramTest::new("bounty_program", program_id, None);
+    program_test.prefer_bpf(true);
+    let mut context = program_test.start_with_context().await;
+    let (creator, claimant) = fund_wallets(&mut context).await;
+    let deadline = deadline(&mut context).await;
+
+    println!();
+    println!("{:<18} {:>8}", "instruction", "units");
+
+    let (create, bounty) = create_sol_bounty(&creator, 1, deadline);
+    measure(&mut context, "create_sol_bounty", create, &creator).await;
+
+    let lock = BountyInstruction::lock_bounty(
+        &program_id,
+        &claimant.pubkey(),
+        &bounty,
+        "https://github.com/solforge/bounties/pull/2".to_string(),
+    )
+    .unwrap();
+    measure(&mut context, "lock_bounty", lock, &claimant).await;
+
+    let claim = BountyInstruction::claim_bounty(&program_id, &claimant.pubkey(), &bounty).unwrap();
+    measure(&mut context, "claim_bounty", claim, &claimant).await;
+
+    let complete = BountyInstruction::complete_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        &claimant.pubkey(),
+        None,
+    )
+    .unwrap();
+    measure(&mut context, "complete_bounty", complete, &creator).await;
+
+    let (create, bounty) = create_sol_bounty(&creator, 2, deadline);
+    measure(&mut context, "create_sol_bounty", create, &creator).await;
+
+    let cancel = BountyInstruction::cancel_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        &creator.pubkey(),
+        None,
+    )
+    .unwrap();
+    measure(&mut context, "cancel_bounty", cancel, &creator).await;
+}
@@ -0,0 +1,266 @@
+// Batch migration of bounty accounts to the current on-chain layout.
+//
+// Finds every program-owned account that is not already in the current
+// layout and sends `MigrateBounty` or `MigrateWebhookAuthority` instructions
+// for it in small batches. Token bounties of the original program escrowed
+// their tokens in a token account owned by a PDA of the bounty address; the
+// script looks it up so the migration can move the tokens to a token vault.
+//
+// Usage:
+//   node scripts/migrate-bounties.js --program <PROGRAM_ID> --keypair <PAYER_KEYPAIR_JSON> \
+//     [--url <RPC_URL>] [--batch-size 4] [--dry-run]
+const fs = require('fs');
+const {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} = require('@solana/web3.js');
+
+// Indices of the migration instructions in the `BountyInstruction` enum
+const MIGRATE_BOUNTY_INSTRUCTION = 9;
+const MIGRATE_WEBHOOK_AUTHORITY_INSTRUCTION = 10;
+
+// Current account header, see `AccountState` in src/state.rs
+const BOUNTY_DISCRIMINATOR = Buffer.from([237, 16, 105, 198, 19, 69, 242, 234]);
+const BOUNTY_VERSION = 7;
+const BOUNTY_CREATOR_OFFSET = 10;
+const BOUNTY_TOKEN_MINT_OFFSET = 42;
+const WEBHOOK_AUTHORITY_DISCRIMINATOR = Buffer.from([252, 38, 149, 193, 194, 10, 8, 10]);
+const WEBHOOK_AUTHORITY_VERSION = 2;
+const WEBHOOK_AUTHORITY_OFFSET = 9;
+const ORG_AUTHORITY_DISCRIMINATOR = Buffer.from([160, 255, 13, 131, 107, 93, 205, 64]);
+const PROGRAM_CONFIG_DISCRIMINATOR = Buffer.from([196, 210, 90, 231, 144, 149, 140, 63]);
+const ADMIN_PROPOSAL_DISCRIMINATOR = Buffer.from([107, 249, 66, 11, 147, 28, 12, 239]);
+const ROLE_ASSIGNMENT_DISCRIMINATOR = Buffer.from([205, 130, 191, 231, 211, 225, 155, 246]);
+const ALLOWED_MINT_DISCRIMINATOR = Buffer.from([173, 229, 179, 46, 121, 164, 247, 6]);
+const ALLOWED_HOST_DISCRIMINATOR = Buffer.from([2, 45, 73, 255, 118, 175, 23, 243]);
+
+// Headerless webhook authority accounts were allocated with exactly this size
+const LEGACY_WEBHOOK_AUTHORITY_LEN = 105;
+
+function parseArgs(argv) {
+  const args = { url: 'https://api.devnet.solana.com', batchSize: 4, dryRun: false };
+  for (let i = 0; i < argv.length; i++) {
+    switch (argv[i]) {
+      case '--program': args.program = argv[++i]; break;
+      case '--keypair': args.keypair = argv[++i]; break;
+      case '--url': args.url = argv[++i]; break;
+      case '--batch-size': args.batchSize = parseInt(argv[++i], 10); break;
+      case '--dry-run': args.dryRun = true; break;
+      default: throw new Error(`Unknown argument: ${argv[i]}`);
+    }
+  }
+  if (!args.program || !args.keypair) {
+    throw new Error('Both --program and --keypair are required');
+  }
+  return args;
+}
+
+function isWebhookAuthority(data) {
+  return data.length >= 9 && data.subarray(0, 8).equals(WEBHOOK_AUTHORITY_DISCRIMINATOR);
+}
+
+// Version 0 webhook authorities have no header. Bounties of that version are
+// always longer, so the size tells them apart.
+function isLegacyWebhookAuthority(data) {
+  return data.length === LEGACY_WEBHOOK_AUTHORITY_LEN && !data.every((byte) => byte === 0);
+}
+
+function needsMigration(data) {
+  if (data.length >= 9 && data.subarray(0, 8).equals(BOUNTY_DISCRIMINATOR)) {
+    return data[8] !== BOUNTY_VERSION;
+  }
+  if (isWebhookAuthority(data)) {
+    return data[8] !== WEBHOOK_AUTHORITY_VERSION;
+  }
+  if (isLegacyWebhookAuthority(data)) {
+    return true;
+  }
+  // The other account types have had a single layout so far
+  const header = data.subarray(0, 8);
+  if (
+    header.equals(ORG_AUTHORITY_DISCRIMINATOR) ||
+    header.equals(PROGRAM_CONFIG_DISCRIMINATOR) ||
+    header.equals(ADMIN_PROPOSAL_DISCRIMINATOR) ||
+    header.equals(ROLE_ASSIGNMENT_DISCRIMINATOR) ||
+    header.equals(ALLOWED_MINT_DISCRIMINATOR) ||
+    header.equals(ALLOWED_HOST_DISCRIMINATOR)
+  ) {
+    return false;
+  }
+  // Zeroed accounts are closed bounties waiting to be garbage collected
+  return !data.every((byte) => byte === 0);
+}
+
+// Version 0 bounties start with the creator key, version 1 stores it right
+// after the header and later versions at BOUNTY_CREATOR_OFFSET
+function creatorOf(data) {
+  let offset = BOUNTY_CREATOR_OFFSET;
+  if (!data.subarray(0, 8).equals(BOUNTY_DISCRIMINATOR)) {
+    offset = 0;
+  } else if (data[8] === 1) {
+    offset = 9;
+  }
+  return new PublicKey(data.subarray(offset, offset + 32));
+}
+
+// Version 0 and 1 bounties store the token mint as a Borsh option after the
+// strings, later versions at BOUNTY_TOKEN_MINT_OFFSET, all zeros when unset
+function tokenMintOf(data) {
+  if (data.subarray(0, 8).equals(BOUNTY_DISCRIMINATOR) && data[8] !== 1) {
+    const mint = data.subarray(BOUNTY_TOKEN_MINT_OFFSET, BOUNTY_TOKEN_MINT_OFFSET + 32);
+    return mint.every((byte) => byte === 0) ? null : new PublicKey(mint);
+  }
+
+  let offset = data.subarray(0, 8).equals(BOUNTY_DISCRIMINATOR) ? 9 : 0;
+  const skipString = () => { offset += 4 + data.readUInt32LE(offset); };
+  offset += 32 + 8; // creator, amount
+  skipString(); // description
+  offset += 32; // issue hash
+  skipString(); // issue URL
+  skipString(); // repository URL
+  offset += 8 + 1; // deadline, state
+  return data[offset] === 1 ? new PublicKey(data.subarray(offset + 1, offset + 33)) : null;
+}
+
+// Every instruction ends with the program config PDA, see `BountyInstruction`
+function programConfigAccount(programId) {
+  const [config] = PublicKey.findProgramAddressSync([Buffer.from('config')], programId);
+  return { pubkey: config, isSigner: false, isWritable: false };
+}
+
+function migrateWebhookAuthorityInstruction(programId, payer, record, data) {
+  const offset = isWebhookAuthority(data) ? WEBHOOK_AUTHORITY_OFFSET : 0;
+  const authority = new PublicKey(data.subarray(offset, offset + 32));
+
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: record, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      programConfigAccount(programId),
+    ],
+    data: Buffer.concat([Buffer.from([MIGRATE_WEBHOOK_AUTHORITY_INSTRUCTION]), authority.toBuffer()]),
+  });
+}
+
+// Token bounties without a token vault also pass the token program, the mint,
+// their legacy escrow token account and the PDA that owns it
+async function legacyEscrowAccounts(connection, programId, bounty, mint, vault) {
+  if (await connection.getAccountInfo(vault)) {
+    return [];
+  }
+
+  const [escrowAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from('bounty'), bounty.toBuffer()],
+    programId
+  );
+  const { value: escrows } = await connection.getTokenAccountsByOwner(escrowAuthority, { mint });
+  if (escrows.length === 0) {
+    // Finished bounties may have no escrow left, they are migrated without a vault
+    return [];
+  }
+
+  const mintAccount = await connection.getAccountInfo(mint);
+  return [
+    { pubkey: mintAccount.owner, isSigner: false, isWritable: false },
+    { pubkey: mint, isSigner: false, isWritable: false },
+    { pubkey: escrows[0].pubkey, isSigner: false, isWritable: true },
+    { pubkey: escrowAuthority, isSigner: false, isWritable: false },
+  ];
+}
+
+async function migrateBountyInstruction(connection, programId, payer, bounty, data) {
+  const mint = tokenMintOf(data);
+  const [vault] = PublicKey.findProgramAddressSync(
+    [Buffer.from(mint ? 'vault' : 'sol_vault'), bounty.toBuffer()],
+    programId
+  );
+  const tokenAccounts = mint
+    ? await legacyEscrowAccounts(connection, programId, bounty, mint, vault)
+    : [];
+
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: bounty, isSigner: false, isWritable: true },
+      { pubkey: creatorOf(data), isSigner: false, isWritable: true },
+      { pubkey: vault, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...tokenAccounts,
+      programConfigAccount(programId),
+    ],
+    data: Buffer.concat([Buffer.from([MIGRATE_BOUNTY_INSTRUCTION]), bounty.toBuffer()]),
+  });
+}
+
+async function migrateInstruction(connection, programId, payer, pubkey, data) {
+  if (isWebhookAuthority(data) || isLegacyWebhookAuthority(data)) {
+    return migrateWebhookAuthorityInstruction(programId, payer, pubkey, data);
+  }
+  return migrateBountyInstruction(connection, programId, payer, pubkey, data);
+}
+
+async function main() {
+  const args = parseArgs(process.argv.slice(2));
+  const connection = new Connection(args.url, 'confirmed');
+  const programId = new PublicKey(args.program);
+  const payer = Keypair.fromSecretKey(
+    new Uint8Array(JSON.parse(fs.readFileSync(args.keypair, 'utf-8')))
+  );
+
+  const accounts = await connection.getProgramAccounts(programId);
+  const pending = accounts.filter(({ account }) => needsMigration(account.data));
+  console.log(`Found ${pending.length} of ${accounts.length} accounts to migrate`);
+
+  let migrated = 0;
+  const failed = [];
+  for (let i = 0; i < pending.length; i += args.batchSize) {
+    const batch = pending.slice(i, i + args.batchSize);
+    const tx = new Transaction();
+    for (const { pubkey, account } of batch) {
+      tx.add(await migrateInstruction(connection, programId, payer.publicKey, pubkey, account.data));
+    }
+
+    if (args.dryRun) {
+      batch.forEach(({ pubkey }) => console.log(`Would migrate ${pubkey.toBase58()}`));
+      continue;
+    }
+
+    try {
+      const signature = await sendAndConfirmTransaction(connection, tx, [payer]);
+      migrated += batch.length;
+      console.log(`Migrated ${batch.length} accounts: ${signature}`);
+    } catch (err) {
+      // Retry one by one so a single bad account doesn't block the rest of the batch
+      for (const { pubkey, account } of batch) {
+        try {
+          const single = new Transaction().add(
+            await migrateInstruction(connection, programId, payer.publicKey, pubkey, account.data)
+          );
+          await sendAndConfirmTransaction(connection, single, [payer]);
+          migrated += 1;
+        } catch (singleErr) {
+          failed.push(pubkey.toBase58());
+          console.error(`Failed to migrate ${pubkey.toBase58()}: ${singleErr.message}`);
+        }
+      }
+    }
+  }
+
+  console.log(`Done: ${migrated} migrated, ${failed.length} failed`);
+  if (failed.length > 0) {
+    process.exitCode = 1;
+  }
+}
+
+main().catch((err) => {
+  console.error(err);
+  process.exit(1);
+});
@@ -0,0 +1,249 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{ed25519_program, instruction::Instruction, pubkey::Pubkey};
+
+use crate::error::BountyError;
+
+/// Prefix of every signed claimant authorization message
+///
+/// Keeps the message from being valid in any other signing context.
+pub const CLAIMANT_AUTHORIZATION_DOMAIN: &[u8] = b"solforge-bounty:claimant-authorization:v1";
+
+/// Size of the ed25519 instruction header, the signature count and a padding byte
+const ED25519_HEADER_LEN: usize = 2;
+
+/// Size of the offsets describing one signature in an ed25519 instruction
+const ED25519_OFFSETS_LEN: usize = 14;
+
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_SIGNATURE_LEN: usize = 64;
+
+/// Instruction index meaning "this instruction" in ed25519 signature offsets
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// What a claimant lets a relayer do on their behalf
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum ClaimantAction {
+    /// Lock the bounty with this PR URL
+    Lock { pr_url: String },
+    /// Claim the bounty the claimant locked
+    Claim,
+}
+
+/// Off-chain message a claimant signs so a relayer can lock or claim for them
+///
+/// The claimant signs `message()` with their wallet key, and the relayer
+/// submits that signature in an ed25519 program instruction placed right
+/// before the sponsored instruction. The message names the program, the
+/// bounty and the action, so it can't be replayed elsewhere, and stops being
+/// accepted at `expires_at`.
+///
+/// The signed bytes are `CLAIMANT_AUTHORIZATION_DOMAIN` followed by the Borsh
+/// encoding of this struct.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ClaimantAuthorization {
+    pub program_id: Pubkey,
+    pub bounty: Pubkey,
+    pub claimant: Pubkey,
+    pub action: ClaimantAction,
+    /// Unix timestamp after which the authorization is rejected
+    pub expires_at: i64,
+}
+
+impl ClaimantAuthorization {
+    /// Bytes the claimant signs
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = CLAIMANT_AUTHORIZATION_DOMAIN.to_vec();
+        // Serializing into a Vec can't fail
+        self.serialize(&mut message).unwrap();
+        message
+    }
+
+    /// Check that an ed25519 program instruction verifies this authorization
+    ///
+    /// The instruction must verify a single signature by the claimant over
+    /// `message()`, with the key, signature and message all stored in the
+    /// instruction itself. The ed25519 program has already checked the
+    /// signature by the time the bounty program runs.
+    pub fn verify(&self, ed25519_instruction: &Instruction) -> Result<(), BountyError> {
+        if ed25519_instruction.program_id != ed25519_program::id() {
+            return Err(BountyError::InvalidClaimantAuthorization);
+        }
+
+        let data = &ed25519_instruction.data;
+        if data.len() < ED25519_HEADER_LEN + ED25519_OFFSETS_LEN || data[0] != 1 {
+            return Err(BountyError::InvalidClaimantAuthorization);
+        }
+
+        let offset = |index: usize| {
+            let start = ED25519_HEADER_LEN + 2 * index;
+            u16::from_le_bytes([data[start], data[start + 1]])
+        };
+        let signature_offset = offset(0) as usize;
+        let signature_instruction = offset(1);
+        let pubkey_offset = offset(2) as usize;
+        let pubkey_instruction = offset(3);
+        let message_offset = offset(4) as usize;
+        let message_len = offset(5) as usize;
+        let message_instruction = offset(6);
+
+        if signature_instruction != CURRENT_INSTRUCTION
+            || pubkey_instruction != CURRENT_INSTRUCTION
+            || message_instruction != CURRENT_INSTRUCTION
+            || data.len() < signature_offset + ED25519_SIGNATURE_LEN
+        {
+            return Err(BountyError::InvalidClaimantAuthorization);
+        }
+
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN)
+            .ok_or(BountyError::InvalidClaimantAuthorization)?;
+        let message = data
+            .get(message_offset..message_offset + message_len)
+            .ok_or(BountyError::InvalidClaimantAuthorization)?;
+
+        if pubkey != self.claimant.as_ref() || message != self.message().as_slice() {
+            return Err(BountyError::InvalidClaimantAuthorization);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{
+        ed25519_instruction::new_ed25519_instruction,
+        signature::{Keypair, Signer},
+    };
+
+    use super::*;
+
+    /// Offset of the first signature offsets field in the instruction data
+    const OFFSETS_START: usize = ED25519_HEADER_LEN;
+
+    fn authorization(claimant: Pubkey) -> ClaimantAuthorization {
+        ClaimantAuthorization {
+            program_id: crate::id(),
+            bounty: Pubkey::new_unique(),
+            claimant,
+            action: ClaimantAction::Lock { pr_url: "https://github.com/solforge/bounties/pull/2".to_string() },
+            expires_at: 1_700_000_000,
+        }
+    }
+
+    /// An ed25519 program instruction with `signer`'s signature over `message`
+    fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+        let keypair = ed25519_dalek::Keypair::from_bytes(&signer.to_bytes()).unwrap();
+        new_ed25519_instruction(&keypair, message)
+    }
+
+    fn set_offset(instruction: &mut Instruction, index: usize, value: u16) {
+        let start = OFFSETS_START + 2 * index;
+        instruction.data[start..start + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn accepts_the_claimants_signature() {
+        let claimant = Keypair::new();
+        let authorization = authorization(claimant.pubkey());
+        let instruction = ed25519_instruction(&claimant, &authorization.message());
+
+        assert_eq!(authorization.verify(&instruction), Ok(()));
+    }
+
+    #[test]
+    fn rejects_other_programs_and_signature_counts() {
+        let claimant = Keypair::new();
+        let authorization = authorization(claimant.pubkey());
+        let instruction = ed25519_instruction(&claimant, &authorization.message());
+
+        let mut other_program = instruction.clone();
+        other_program.program_id = Pubkey::new_unique();
+        assert_eq!(authorization.verify(&other_program), Err(BountyError::InvalidClaimantAuthorization));
+
+        for count in [0, 2] {
+            let mut signatures = instruction.clone();
+            signatures.data[0] = count;
+            assert_eq!(authorization.verify(&signatures), Err(BountyError::InvalidClaimantAuthorization));
+        }
+
+        let mut truncated = instruction;
+        truncated.data.truncate(ED25519_HEADER_LEN + ED25519_OFFSETS_LEN - 1);
+        assert_eq!(authorization.verify(&truncated), Err(BountyError::InvalidClaimantAuthorization));
+    }
+
+    #[test]
+    fn rejects_data_read_from_other_instructions() {
+        let claimant = Keypair::new();
+        let authorization = authorization(claimant.pubkey());
+        let instruction = ed25519_instruction(&claimant, &authorization.message());
+
+        // Signature, public key and message instruction indexes must all be `CURRENT_INSTRUCTION`
+        for index in [1, 3, 6] {
+            for instruction_index in [0, 1, u16::MAX - 1] {
+                let mut other = instruction.clone();
+                set_offset(&mut other, index, instruction_index);
+                assert_eq!(
+                    authorization.verify(&other),
+                    Err(BountyError::InvalidClaimantAuthorization),
+                    "offset {} set to {}",
+                    index,
+                    instruction_index
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_other_signers() {
+        let claimant = Keypair::new();
+        let other = Keypair::new();
+        let authorization = authorization(claimant.pubkey());
+
+        let signed_by_other = ed25519_instruction(&other, &authorization.message());
+        assert_eq!(authorization.verify(&signed_by_other), Err(BountyError::InvalidClaimantAuthorization));
+
+        // Pointing the public key offset elsewhere, here at the signature
+        let mut moved_pubkey = ed25519_instruction(&claimant, &authorization.message());
+        let signature_offset =
+            u16::from_le_bytes([moved_pubkey.data[OFFSETS_START], moved_pubkey.data[OFFSETS_START + 1]]);
+        set_offset(&mut moved_pubkey, 2, signature_offset);
+        assert_eq!(authorization.verify(&moved_pubkey), Err(BountyError::InvalidClaimantAuthorization));
+
+        let mut out_of_bounds = ed25519_instruction(&claimant, &authorization.message());
+        set_offset(&mut out_of_bounds, 2, u16::MAX);
+        assert_eq!(authorization.verify(&out_of_bounds), Err(BountyError::InvalidClaimantAuthorization));
+    }
+
+    #[test]
+    fn rejects_any_other_message() {
+        let claimant = Keypair::new();
+        let authorization = authorization(claimant.pubkey());
+
+        let mut claim = authorization.clone();
+        claim.action = ClaimantAction::Claim;
+        let mut other_bounty = authorization.clone();
+        other_bounty.bounty = Pubkey::new_unique();
+        let mut later = authorization.clone();
+        later.expires_at += 1;
+        let mut other_program = authorization.clone();
+        other_program.program_id = Pubkey::new_unique();
+
+        for other in [claim, other_bounty, later, other_program] {
+            let instruction = ed25519_instruction(&claimant, &other.message());
+            assert_eq!(authorization.verify(&instruction), Err(BountyError::InvalidClaimantAuthorization));
+        }
+
+        // A message without the domain prefix
+        let mut undomained = Vec::new();
+        authorization.serialize(&mut undomained).unwrap();
+        let instruction = ed25519_instruction(&claimant, &undomained);
+        assert_eq!(authorization.verify(&instruction), Err(BountyError::InvalidClaimantAuthorization));
+
+        // The right message with its last byte cut off
+        let mut shortened = ed25519_instruction(&claimant, &authorization.message());
+        let message_len = authorization.message().len() as u16;
+        set_offset(&mut shortened, 5, message_len - 1);
+        assert_eq!(authorization.verify(&shortened), Err(BountyError::InvalidClaimantAuthorization));
+    }
+}
@@ -64,6 +64,94 @@ pub enum BountyError {
     /// Bounty for this issue already exists
     #[error("Bounty for this issue already exists")]
     BountyAlreadyExists,
+
+    /// Bounty cannot be closed until its retention period has elapsed
+    #[error("Bounty retention period has not elapsed")]
+    RetentionPeriodActive,
+
+    /// Account discriminator does not match the expected account type
+    #[error("Account type does not match the expected discriminator")]
+    InvalidAccountDiscriminator,
+
+    /// Account layout version is not supported by this program
+    #[error("Unsupported account layout version")]
+    UnsupportedAccountVersion,
+
+    /// URL does not use the https scheme
+    #[error("URL must use https")]
+    InvalidUrlScheme,
+
+    /// URL host is not on the allowlist
+    #[error("URL host is not allowed")]
+    UrlHostNotAllowed,
+
+    /// URL, or a name in it, exceeds the length limit
+    #[error("URL is too long")]
+    UrlTooLong,
+
+    /// URL path is not a repository, issue or pull request path
+    #[error("Malformed URL")]
+    MalformedUrl,
+
+    /// Repository URL does not point to a repository
+    #[error("Invalid repository URL")]
+    InvalidRepositoryUrl,
+
+    /// Issue URL does not point to an issue
+    #[error("Invalid issue URL")]
+    InvalidIssueUrl,
+
+    /// PR URL does not point to a pull request
+    #[error("Invalid pull request URL")]
+    InvalidPullRequestUrl,
+
+    /// Issue does not belong to the bounty's repository
+    #[error("Issue is not in the bounty's repository")]
+    IssueNotInRepository,
+
+    /// Pull request does not belong to the bounty's repository
+    #[error("Pull request is not in the bounty's repository")]
+    PullRequestNotInRepository,
+
+    /// Amount change is zero or would leave the bounty without funds
+    #[error("Invalid bounty amount")]
+    InvalidAmount,
+
+    /// Creator already has the maximum number of delegates
+    #[error("Too many delegates")]
+    TooManyDelegates,
+
+    /// Sponsored instruction isn't preceded by a matching claimant signature
+    #[error("Invalid claimant authorization")]
+    InvalidClaimantAuthorization,
+
+    /// Claimant authorization is past its expiry
+    #[error("Claimant authorization has expired")]
+    ClaimantAuthorizationExpired,
+
+    /// Program is paused by the admin
+    #[error("Program is paused")]
+    ProgramPaused,
+
+    /// Instruction is disabled by the admin
+    #[error("Instruction is disabled")]
+    InstructionDisabled,
+
+    /// Admin action must be proposed and executed after a delay
+    #[error("Action requires a timelocked proposal")]
+    TimelockRequired,
+
+    /// Proposal delay has not passed yet
+    #[error("Proposal is still timelocked")]
+    TimelockActive,
+
+    /// Token account holds a different mint than the bounty
+    #[error("Token account mint mismatch")]
+    TokenAccountMintMismatch,
+
+    /// Token account is owned by someone other than the expected party
+    #[error("Token account owner mismatch")]
+    TokenAccountOwnerMismatch,
 }
 
 impl From<BountyError> for ProgramError {
@@ -1,12 +1,18 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    bpf_loader_upgradeable,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program,
-    sysvar::rent,
+    sysvar,
     program_error::ProgramError,
 };
 
+use crate::{
+    state::{hash_host, AdminAction, DelegatePermissions, ForgePlatform, Role},
+    url::ForgeUrl,
+};
+
 /// Seeds used for the bounty PDA derivation
 pub const BOUNTY_SEED_PREFIX: &[u8] = b"bounty";
 
@@ -26,20 +32,173 @@ pub fn find_bounty_address(
     )
 }
 
+/// Seeds used for the SOL vault PDA derivation
+pub const SOL_VAULT_SEED_PREFIX: &[u8] = b"sol_vault";
+
+/// Helper function to find the system-owned vault PDA that escrows a SOL bounty
+pub fn find_sol_vault_address(
+    program_id: &Pubkey,
+    bounty_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SOL_VAULT_SEED_PREFIX,
+            bounty_address.as_ref(),
+        ],
+        program_id
+    )
+}
+
+/// Seeds used for the token vault PDA derivation
+pub const TOKEN_VAULT_SEED_PREFIX: &[u8] = b"vault";
+
 /// Helper function to find the token vault PDA for a bounty
+///
+/// The vault is a token account owned by the bounty PDA itself, or by the
+/// vault for bounties at a legacy address.
 pub fn find_token_vault_address(
     program_id: &Pubkey,
     bounty_address: &Pubkey,
 ) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
-            b"vault",
+            TOKEN_VAULT_SEED_PREFIX,
+            bounty_address.as_ref(),
+        ],
+        program_id
+    )
+}
+
+/// Helper function to find the PDA that owned the escrow of a legacy token bounty
+///
+/// The original program moved the tokens of bounties created at a client-chosen
+/// address to a token account of the creator's choosing, signing for it with
+/// this PDA. `MigrateBounty` moves them to the token vault.
+pub fn find_legacy_escrow_authority_address(
+    program_id: &Pubkey,
+    bounty_address: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            BOUNTY_SEED_PREFIX,
             bounty_address.as_ref(),
         ],
         program_id
     )
 }
 
+/// Token program and mint of a token bounty
+///
+/// Instructions that move a token bounty's funds take both after its vault.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BountyToken {
+    /// SPL Token or Token-2022, whichever the mint belongs to
+    pub program: Pubkey,
+    pub mint: Pubkey,
+}
+
+/// Seeds used for the org authority PDA derivation
+pub const ORG_AUTHORITY_SEED_PREFIX: &[u8] = b"org_authority";
+
+/// Helper function to find the PDA listing a creator's delegates
+pub fn find_org_authority_address(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ORG_AUTHORITY_SEED_PREFIX,
+            creator.as_ref(),
+        ],
+        program_id
+    )
+}
+
+/// Seeds used for the program config PDA derivation
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"config";
+
+/// Helper function to find the PDA holding the program config
+pub fn find_program_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], program_id)
+}
+
+/// Helper function to find the upgradeable loader's program data account, which stores the upgrade authority
+pub fn find_program_data_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id())
+}
+
+/// Seeds used for the admin proposal PDA derivation
+pub const PROPOSAL_SEED_PREFIX: &[u8] = b"proposal";
+
+/// Helper function to find the PDA holding an admin proposal
+pub fn find_proposal_address(program_id: &Pubkey, proposal_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROPOSAL_SEED_PREFIX,
+            &proposal_id.to_le_bytes(),
+        ],
+        program_id
+    )
+}
+
+/// Seeds used for the role assignment PDA derivation
+pub const ROLE_SEED_PREFIX: &[u8] = b"role";
+
+/// Helper function to find the PDA assigning `role` to `holder`
+pub fn find_role_address(program_id: &Pubkey, role: Role, holder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROLE_SEED_PREFIX,
+            &[role as u8],
+            holder.as_ref(),
+        ],
+        program_id
+    )
+}
+
+/// Seeds used for the allowed mint PDA derivation
+pub const ALLOWED_MINT_SEED_PREFIX: &[u8] = b"allowed_mint";
+
+/// Helper function to find the PDA allowing token bounties in a mint
+pub fn find_allowed_mint_address(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ALLOWED_MINT_SEED_PREFIX,
+            mint.as_ref(),
+        ],
+        program_id
+    )
+}
+
+/// Seeds used for the allowed host PDA derivation
+pub const ALLOWED_HOST_SEED_PREFIX: &[u8] = b"allowed_host";
+
+/// Helper function to find the PDA allowing bounties on a self-hosted forge instance
+pub fn find_allowed_host_address(program_id: &Pubkey, platform: ForgePlatform, host: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ALLOWED_HOST_SEED_PREFIX,
+            &[platform as u8],
+            &hash_host(host),
+        ],
+        program_id
+    )
+}
+
+/// Every instruction except the admin ones (see `BountyInstruction::is_admin`)
+/// takes the program config PDA as its last account, after any optional ones.
+/// It may be uninitialized.
+///
+/// Instructions that create accounts take an optional payer for their rent
+/// right after the required accounts, so a relayer can sponsor it. Optional
+/// accounts that are left out but followed by others are passed as the
+/// program id instead.
+///
+/// Instructions that let a creator's delegates act instead of the creator
+/// take the creator's org authority PDA as an optional account, just before
+/// the program config, see `BountyInstruction::with_org_authority`. The
+/// delegate then signs in place of the creator, and any funds it releases can
+/// only go to the claimant or back to the creator.
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub enum BountyInstruction {
     /// Creates a new SOL bounty
@@ -47,7 +206,11 @@ pub enum BountyInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The bounty creator
     /// 1. `[writable]` The bounty account to create
-    /// 2. `[]` System program
+    /// 2. `[writable]` The bounty's SOL vault PDA
+    /// 3. `[]` System program
+    /// 4. `[signer, writable]` The rent payer (optional, defaults to the creator)
+    /// 5. `[]` The allowed host PDA (if the repository is self-hosted)
+    /// 6. `[]` The creator's org authority PDA (if a delegate with `can_create` signs)
     CreateSolBounty {
         amount: u64,
         description: String,
@@ -55,18 +218,26 @@ pub enum BountyInstruction {
         issue_url: String,
         repository_url: String,
         deadline: i64,
+        platform: ForgePlatform,
     },
 
     /// Creates a new SPL Token bounty
+    ///
+    /// Token-2022 mints are supported. With the transfer fee extension, the
+    /// bounty escrows `amount` minus the fee, which is what the vault receives.
     /// 
     /// Accounts expected:
     /// 0. `[signer]` The bounty creator
     /// 1. `[writable]` The bounty account to create
-    /// 2. `[writable]` The creator's token account to transfer from
-    /// 3. `[writable]` The bounty's token account to transfer to
+    /// 2. `[writable]` The signer's token account to transfer from
+    /// 3. `[writable]` The bounty's token vault PDA to create
     /// 4. `[]` The token mint
-    /// 5. `[]` Token program
+    /// 5. `[]` SPL Token or Token-2022 program
     /// 6. `[]` System program
+    /// 7. `[]` The mint's allowed mint PDA
+    /// 8. `[signer, writable]` The rent payer (optional, defaults to the creator)
+    /// 9. `[]` The allowed host PDA (if the repository is self-hosted)
+    /// 10. `[]` The creator's org authority PDA (if a delegate with `can_create` signs)
     CreateTokenBounty {
         amount: u64,
         description: String,
@@ -75,13 +246,19 @@ pub enum BountyInstruction {
         repository_url: String,
         deadline: i64,
         token_mint: Pubkey,
+        platform: ForgePlatform,
     },
 
     /// Locks a bounty for claiming
+    ///
+    /// `pr_url` must be a pull request in the bounty's repository, or a merge
+    /// request on GitLab. The bounty account is reallocated to fit the PR URL, with the claimant
+    /// paying any extra rent.
     /// 
     /// Accounts expected:
-    /// 0. `[signer]` The claimant
+    /// 0. `[signer, writable]` The claimant
     /// 1. `[writable]` The bounty account
+    /// 2. `[]` System program
     LockBounty {
         bounty_pubkey: Pubkey,
         pr_url: String,
@@ -101,8 +278,11 @@ pub enum BountyInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The bounty creator
     /// 1. `[writable]` The bounty account
-    /// 2. `[writable]` The creator's refund account (native SOL or token account)
-    /// 3. `[]` Token program (if token bounty)
+    /// 2. `[writable]` The creator's refund account (the creator, or its token account)
+    /// 3. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 4. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 5. `[]` The token mint (if token bounty)
+    /// 6. `[]` The creator's org authority PDA (if a delegate with `can_cancel` signs)
     CancelBounty {
         bounty_pubkey: Pubkey,
     },
@@ -112,35 +292,421 @@ pub enum BountyInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The bounty creator
     /// 1. `[writable]` The bounty account
-    /// 2. `[writable]` The claimant's reward account (native SOL or token account)
-    /// 3. `[]` Token program (if token bounty)
+    /// 2. `[writable]` The claimant's reward account (the claimant, or its token account)
+    /// 3. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 4. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 5. `[]` The token mint (if token bounty)
+    /// 6. `[]` The creator's org authority PDA (if a delegate with `can_complete` signs)
     CompleteBounty {
         bounty_pubkey: Pubkey,
     },
     
     /// Automatically completes a bounty when a PR is merged
-    /// This can only be called by authorized GitHub webhook handlers
+    /// This can only be called by authorized forge webhook handlers
     /// 
     /// Accounts expected:
     /// 0. `[signer]` The webhook authority (must be on allowlist)
-    /// 1. `[writable]` The bounty account
-    /// 2. `[writable]` The claimant's reward account (native SOL or token account)
-    /// 3. `[]` Token program (if token bounty)
+    /// 1. `[]` The webhook authority record (PDA)
+    /// 2. `[writable]` The bounty account
+    /// 3. `[writable]` The claimant's reward account (the claimant, or its token account)
+    /// 4. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 5. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 6. `[]` The token mint (if token bounty)
+    /// 7. `[writable]` The fee collector, or its token account (if the bounty charges a fee)
     AutoCompleteBounty {
         bounty_pubkey: Pubkey,
         pr_url: String,
     },
 
     /// Adds a new authorized webhook caller
-    /// 
-    /// Accounts expected:
-    /// 0. `[signer]` The admin authority (initially hardcoded)
-    /// 1. `[writable]` The authority account to create (PDA)
-    /// 2. `[]` System program
+    ///
+    /// Always fails with `TimelockRequired`, webhook authorities are added by
+    /// executing a `ProposeAction` with `AdminAction::AddWebhookAuthority`.
+    /// Kept so the indices of later instructions don't change.
     AddWebhookAuthority {
         authority: Pubkey,
         name: String,
     },
+
+    /// Closes a completed or cancelled bounty and returns its rent to the creator
+    ///
+    /// Funds left in the vault, such as tokens anyone sent to it, go to the
+    /// creator's refund account.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The bounty creator (receives the rent)
+    /// 1. `[writable]` The bounty account to close
+    /// 2. `[writable]` The creator's refund account (the creator, or its token account)
+    /// 3. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 4. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 5. `[writable]` The token mint (if token bounty)
+    CloseBounty {
+        bounty_pubkey: Pubkey,
+    },
+
+    /// Rewrites a bounty stored in an older layout in the current one
+    ///
+    /// Reallocates the account as needed. The payer covers any extra rent and
+    /// rent freed by shrinking the account goes back to the creator. Legacy SOL
+    /// bounties that escrow their amount in the bounty account have it moved
+    /// to the SOL vault. Legacy token bounties without a token vault get one,
+    /// funded with the balance of their legacy escrow, see
+    /// `find_legacy_escrow_authority_address`. Bounties at an address other
+    /// than their PDA are marked as such. Bounties already in the current
+    /// layout are left as is.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer
+    /// 1. `[writable]` The bounty account
+    /// 2. `[writable]` The bounty creator
+    /// 3. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 4. `[]` System program
+    /// 5. `[]` The mint's token program (if token bounty without a token vault)
+    /// 6. `[]` The token mint (if token bounty without a token vault)
+    /// 7. `[writable]` The legacy escrow token account (if token bounty without a token vault)
+    /// 8. `[]` The legacy escrow authority PDA (if token bounty without a token vault)
+    MigrateBounty {
+        bounty_pubkey: Pubkey,
+    },
+
+    /// Rewrites a webhook authority record stored in an older layout in the current one
+    ///
+    /// The payer covers any extra rent. Records already in the current layout
+    /// are left as is.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The payer
+    /// 1. `[writable]` The webhook authority record (PDA)
+    /// 2. `[]` System program
+    MigrateWebhookAuthority {
+        authority: Pubkey,
+    },
+
+    /// Updates the description and URLs of an available bounty
+    ///
+    /// Fields left as `None` are unchanged. The issue URL may only change in
+    /// ways that keep the same canonical issue, see `IssueId`. The bounty
+    /// account is reallocated to fit, with the signer paying any extra rent
+    /// and the creator receiving any freed rent.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The bounty creator
+    /// 1. `[writable]` The bounty account
+    /// 2. `[writable]` The creator, receiving any freed rent (differs from the signer if a delegate signs)
+    /// 3. `[]` System program
+    /// 4. `[]` The allowed host PDA (if `repository_url` is set to a self-hosted repository)
+    /// 5. `[]` The creator's org authority PDA (if a delegate with `can_extend` signs)
+    UpdateBountyMetadata {
+        bounty_pubkey: Pubkey,
+        description: Option<String>,
+        issue_url: Option<String>,
+        repository_url: Option<String>,
+    },
+
+    /// Adds `amount` to the escrow of a bounty that is not frozen, completed or cancelled
+    ///
+    /// Mints with a transfer fee add only what reaches the vault.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The bounty creator
+    /// 1. `[writable]` The bounty account
+    /// 2. `[writable]` The account to fund from: a signing wallet, usually the creator (if SOL bounty), or a token account the signer owns (if token bounty)
+    /// 3. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 4. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 5. `[]` The token mint (if token bounty)
+    /// 6. `[]` The creator's org authority PDA (if a delegate with `can_extend` signs)
+    TopUpBounty {
+        bounty_pubkey: Pubkey,
+        amount: u64,
+    },
+
+    /// Removes `amount` from the escrow of an available bounty and refunds it
+    ///
+    /// The bounty must keep a non-zero amount, cancel it to withdraw everything.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The bounty creator
+    /// 1. `[writable]` The bounty account
+    /// 2. `[writable]` The creator's refund account (the creator, or its token account)
+    /// 3. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 4. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 5. `[]` The token mint (if token bounty)
+    /// 6. `[]` The creator's org authority PDA (if a delegate with `can_cancel` signs)
+    ReduceBounty {
+        bounty_pubkey: Pubkey,
+        amount: u64,
+    },
+
+    /// Offers ownership of a bounty to `new_creator`
+    ///
+    /// Ownership only changes once `new_creator` accepts with
+    /// `AcceptBountyOwnership`. Offering it to the current creator withdraws a
+    /// pending offer. The bounty keeps its address, which stays derived from
+    /// the original creator.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The bounty creator
+    /// 1. `[writable]` The bounty account
+    TransferBountyOwnership {
+        bounty_pubkey: Pubkey,
+        new_creator: Pubkey,
+    },
+
+    /// Accepts a pending ownership offer, making the signer the bounty creator
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The new creator
+    /// 1. `[writable]` The bounty account
+    AcceptBountyOwnership {
+        bounty_pubkey: Pubkey,
+    },
+
+    /// Grants a delegate permissions to manage the creator's bounties
+    ///
+    /// Replaces any permissions the delegate had, and removes the delegate
+    /// when none are granted. Creates the creator's org authority PDA on first
+    /// use.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The bounty creator
+    /// 1. `[writable]` The creator's org authority PDA
+    /// 2. `[]` System program
+    /// 3. `[signer, writable]` The rent payer (optional, defaults to the creator)
+    SetBountyDelegate {
+        delegate: Pubkey,
+        permissions: DelegatePermissions,
+    },
+
+    /// Locks a bounty for a claimant who authorized it off-chain
+    ///
+    /// Same as `LockBounty`, but a relayer signs and pays the rent. The
+    /// instruction right before this one must be an ed25519 program
+    /// instruction verifying the claimant's signature over a
+    /// `ClaimantAuthorization` for this bounty with `ClaimantAction::Lock`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The relayer
+    /// 1. `[writable]` The bounty account
+    /// 2. `[]` System program
+    /// 3. `[]` Instructions sysvar
+    SponsoredLockBounty {
+        bounty_pubkey: Pubkey,
+        claimant: Pubkey,
+        pr_url: String,
+        expires_at: i64,
+    },
+
+    /// Claims a locked bounty for a claimant who authorized it off-chain
+    ///
+    /// Same as `ClaimBounty`, authorized by an ed25519 program instruction
+    /// right before this one, see `SponsoredLockBounty`, with
+    /// `ClaimantAction::Claim`.
+    ///
+    /// Accounts expected:
+    /// 0. `[writable]` The bounty account
+    /// 1. `[]` Instructions sysvar
+    SponsoredClaimBounty {
+        bounty_pubkey: Pubkey,
+        claimant: Pubkey,
+        expires_at: i64,
+    },
+
+    /// Pauses the program or disables individual instructions
+    ///
+    /// `disabled_instructions` has bit `n` set to disable the instruction
+    /// with index `n` in this enum. While paused, only withdrawals by
+    /// creators (`CancelBounty`, `ReduceBounty` and `CloseBounty`) and
+    /// moderation remain available, unless disabled individually. This instruction itself is
+    /// never paused. The config must have been created with `InitializeProgramConfig`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The admin
+    /// 1. `[]` The admin's `Role::Admin` assignment PDA (uninitialized for the root admin)
+    /// 2. `[writable]` The program config PDA
+    SetProgramConfig {
+        paused: bool,
+        disabled_instructions: u64,
+    },
+
+    /// Proposes a trust change, executable after `ProgramConfig::ACTION_DELAY`
+    ///
+    /// Stores the action in a proposal PDA, see `find_proposal_address`,
+    /// with the id taken from the config's `next_proposal_id`. Like
+    /// `SetProgramConfig`, this instruction is never paused and needs the
+    /// config to exist. The signer needs the action's
+    /// `AdminAction::required_role`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The proposer
+    /// 1. `[]` The proposer's role assignment PDA for the required role
+    /// 2. `[writable]` The program config PDA
+    /// 3. `[writable]` The proposal PDA to create
+    /// 4. `[]` System program
+    /// 5. `[signer, writable]` The rent payer (optional, defaults to the proposer)
+    ProposeAction {
+        action: AdminAction,
+    },
+
+    /// Executes a proposal whose delay has passed and closes it
+    ///
+    /// The signer needs the action's `AdminAction::required_role`, and
+    /// receives the proposal's rent.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The executor
+    /// 1. `[]` The executor's role assignment PDA for the required role
+    /// 2. `[writable]` The program config PDA
+    /// 3. `[writable]` The proposal PDA
+    /// 4. `[]` System program
+    /// 5. `[writable]` The webhook authority, role assignment or allowed mint PDA the action writes (if any)
+    /// 6. `[signer, writable]` The rent payer (optional, defaults to the executor)
+    ExecuteAction {
+        proposal_id: u64,
+    },
+
+    /// Cancels a pending proposal and closes it, refunding its rent to the admin
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The admin
+    /// 1. `[]` The admin's `Role::Admin` assignment PDA (uninitialized for the root admin)
+    /// 2. `[]` The program config PDA
+    /// 3. `[writable]` The proposal PDA
+    CancelAction {
+        proposal_id: u64,
+    },
+
+    /// Grants a role other than `Role::Admin`, which needs a timelocked
+    /// `AdminAction::GrantRole`
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The admin
+    /// 1. `[]` The admin's `Role::Admin` assignment PDA (uninitialized for the root admin)
+    /// 2. `[]` The program config PDA
+    /// 3. `[writable]` The role assignment PDA to create
+    /// 4. `[]` System program
+    /// 5. `[signer, writable]` The rent payer (optional, defaults to the admin)
+    GrantRole {
+        role: Role,
+        holder: Pubkey,
+    },
+
+    /// Revokes a role immediately, closing its assignment and refunding its rent to the admin
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The admin
+    /// 1. `[]` The admin's `Role::Admin` assignment PDA (uninitialized for the root admin)
+    /// 2. `[]` The program config PDA
+    /// 3. `[writable]` The role assignment PDA to close
+    RevokeRole {
+        role: Role,
+        holder: Pubkey,
+    },
+
+    /// Freezes an open bounty, blocking locking, claiming and completion
+    ///
+    /// Needs `Role::Moderator`. Like withdrawals, moderation stays available
+    /// while the program is paused.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The moderator
+    /// 1. `[]` The moderator's `Role::Moderator` assignment PDA
+    /// 2. `[writable]` The bounty account
+    FreezeBounty {
+        bounty_pubkey: Pubkey,
+    },
+
+    /// Returns a frozen bounty to the state it was frozen in
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The moderator
+    /// 1. `[]` The moderator's `Role::Moderator` assignment PDA
+    /// 2. `[writable]` The bounty account
+    UnfreezeBounty {
+        bounty_pubkey: Pubkey,
+    },
+
+    /// Cancels an open or frozen bounty, refunding the creator
+    ///
+    /// `reason` is a non-zero code recorded on the bounty, see
+    /// `BountyHeader::moderation_reason`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The moderator
+    /// 1. `[]` The moderator's `Role::Moderator` assignment PDA
+    /// 2. `[writable]` The bounty account
+    /// 3. `[writable]` The creator's refund account (the creator, or its token account)
+    /// 4. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 5. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 6. `[]` The token mint (if token bounty)
+    ForceCancelBounty {
+        bounty_pubkey: Pubkey,
+        reason: u8,
+    },
+
+    /// Removes a mint from the allowlist immediately, refunding its rent to the admin
+    ///
+    /// Existing bounties in the mint are unaffected. Mints are added with a
+    /// timelocked `AdminAction::AllowMint`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The admin
+    /// 1. `[]` The admin's `Role::Admin` assignment PDA (uninitialized for the root admin)
+    /// 2. `[]` The program config PDA
+    /// 3. `[writable]` The allowed mint PDA to close
+    DisallowMint {
+        mint: Pubkey,
+    },
+
+    /// Removes a self-hosted forge instance from the allowlist immediately, refunding its rent to the admin
+    ///
+    /// Existing bounties on the host are unaffected. Hosts are added with a
+    /// timelocked `AdminAction::AllowHost`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The admin
+    /// 1. `[]` The admin's `Role::Admin` assignment PDA (uninitialized for the root admin)
+    /// 2. `[]` The program config PDA
+    /// 3. `[writable]` The allowed host PDA to close
+    DisallowHost {
+        platform: ForgePlatform,
+        host: String,
+    },
+
+    /// Creates the program config PDA with `admin` as its root admin
+    ///
+    /// Only the program's upgrade authority can call it, once, after the
+    /// first deployment. Until then nothing is paused and no instruction
+    /// that needs a role works. The root admin can be rotated afterwards
+    /// with a timelocked `AdminAction::SetAdmin`.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The program's upgrade authority
+    /// 1. `[]` The program's program data account, see `find_program_data_address`
+    /// 2. `[writable]` The program config PDA
+    /// 3. `[]` System program
+    /// 4. `[signer, writable]` The rent payer (optional, defaults to the upgrade authority)
+    InitializeProgramConfig {
+        admin: Pubkey,
+    },
+
+    /// Settles a dispute over a claimed bounty its creator hasn't completed
+    ///
+    /// Needs `Role::Arbiter`. Awarding the bounty completes it and pays the
+    /// claimant, otherwise it's cancelled and the creator refunded.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The arbiter
+    /// 1. `[]` The arbiter's `Role::Arbiter` assignment PDA
+    /// 2. `[writable]` The bounty account
+    /// 3. `[writable]` The claimant's reward account if awarded, the creator's refund account otherwise
+    ///    (the wallet, or its token account)
+    /// 4. `[writable]` The bounty's SOL vault PDA (if SOL bounty), or its token vault PDA (if token bounty)
+    /// 5. `[]` System program (if SOL bounty), or the mint's token program, SPL Token or Token-2022 (if token bounty)
+    /// 6. `[]` The token mint (if token bounty)
+    ResolveDispute {
+        bounty_pubkey: Pubkey,
+        award_claimant: bool,
+    },
 }
 
 impl BountyInstruction {
@@ -154,34 +720,34 @@ impl BountyInstruction {
         issue_url: String,
         repository_url: String,
         deadline: i64,
-        fee_collector: Option<Pubkey>,
-        _fee_percentage: u8,
+        platform: ForgePlatform,
+        payer: Option<&Pubkey>,
     ) -> Result<Instruction, ProgramError> {
         let data = Self::CreateSolBounty {
             amount,
             description,
             issue_hash,
             issue_url,
-            repository_url,
+            repository_url: repository_url.clone(),
             deadline,
+            platform,
         };
         let data = borsh::to_vec(&data)?;
-        
-        Ok(Instruction {
+
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(find_sol_vault_address(program_id, bounty_account).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        Self::push_payer(program_id, payer, &mut accounts);
+        Self::push_allowed_host(program_id, platform, Some(&repository_url), &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
-            accounts: vec![
-                AccountMeta::new(*creator, true),
-                AccountMeta::new(*bounty_account, false),
-                AccountMeta::new_readonly(system_program::id(), false),
-                AccountMeta::new_readonly(rent::id(), false),
-                if let Some(fee_pubkey) = fee_collector {
-                    AccountMeta::new_readonly(fee_pubkey, false)
-                } else {
-                    AccountMeta::new_readonly(*creator, false)
-                },
-            ],
+            accounts,
             data,
-        })
+        }))
     }
 
     pub fn create_token_bounty(
@@ -189,7 +755,6 @@ impl BountyInstruction {
         creator: &Pubkey,
         bounty_account: &Pubkey,
         creator_token: &Pubkey,
-        bounty_token: &Pubkey,
         token_mint: &Pubkey,
         token_program: &Pubkey,
         amount: u64,
@@ -198,32 +763,41 @@ impl BountyInstruction {
         issue_url: String,
         repository_url: String,
         deadline: i64,
+        platform: ForgePlatform,
+        payer: Option<&Pubkey>,
     ) -> Result<Instruction, ProgramError> {
         let data = Self::CreateTokenBounty {
             amount,
             description,
             issue_hash,
             issue_url,
-            repository_url,
+            repository_url: repository_url.clone(),
             deadline,
             token_mint: *token_mint,
+            platform,
         };
         let data = borsh::to_vec(&data)?;
-        
-        Ok(Instruction {
+
+        let (token_vault, _) = find_token_vault_address(program_id, bounty_account);
+
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*creator_token, false),
+            AccountMeta::new(token_vault, false),
+            AccountMeta::new_readonly(*token_mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(find_allowed_mint_address(program_id, token_mint).0, false),
+        ];
+        Self::push_payer(program_id, payer, &mut accounts);
+        Self::push_allowed_host(program_id, platform, Some(&repository_url), &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
-            accounts: vec![
-                AccountMeta::new(*creator, true),
-                AccountMeta::new(*bounty_account, false),
-                AccountMeta::new(*creator_token, false),
-                AccountMeta::new(*bounty_token, false),
-                AccountMeta::new_readonly(*token_mint, false),
-                AccountMeta::new_readonly(*token_program, false),
-                AccountMeta::new_readonly(system_program::id(), false),
-                AccountMeta::new_readonly(rent::id(), false),
-            ],
+            accounts,
             data,
-        })
+        }))
     }
 
     pub fn lock_bounty(
@@ -238,14 +812,15 @@ impl BountyInstruction {
         };
         let data = borsh::to_vec(&data)?;
         
-        Ok(Instruction {
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
             accounts: vec![
                 AccountMeta::new(*claimant, true),
                 AccountMeta::new(*bounty_account, false),
+                AccountMeta::new_readonly(system_program::id(), false),
             ],
             data,
-        })
+        }))
     }
 
     pub fn claim_bounty(
@@ -258,14 +833,14 @@ impl BountyInstruction {
         };
         let data = borsh::to_vec(&data)?;
         
-        Ok(Instruction {
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
             accounts: vec![
                 AccountMeta::new(*claimant, true),
                 AccountMeta::new(*bounty_account, false),
             ],
             data,
-        })
+        }))
     }
 
     pub fn cancel_bounty(
@@ -273,7 +848,7 @@ impl BountyInstruction {
         creator: &Pubkey,
         bounty_account: &Pubkey,
         refund_account: &Pubkey,
-        token_program: Option<&Pubkey>,
+        token: Option<&BountyToken>,
     ) -> Result<Instruction, ProgramError> {
         let data = borsh::to_vec(&Self::CancelBounty {
             bounty_pubkey: *bounty_account,
@@ -285,15 +860,13 @@ impl BountyInstruction {
             AccountMeta::new(*refund_account, false),
         ];
 
-        if let Some(token_program) = token_program {
-            accounts.push(AccountMeta::new_readonly(*token_program, false));
-        }
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
 
-        Ok(Instruction {
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
             accounts,
             data,
-        })
+        }))
     }
 
     pub fn complete_bounty(
@@ -301,7 +874,7 @@ impl BountyInstruction {
         creator: &Pubkey,
         bounty_account: &Pubkey,
         reward_account: &Pubkey,
-        token_program: Option<&Pubkey>,
+        token: Option<&BountyToken>,
     ) -> Result<Instruction, ProgramError> {
         let data = borsh::to_vec(&Self::CompleteBounty {
             bounty_pubkey: *bounty_account,
@@ -313,15 +886,13 @@ impl BountyInstruction {
             AccountMeta::new(*reward_account, false),
         ];
 
-        if let Some(token_program) = token_program {
-            accounts.push(AccountMeta::new_readonly(*token_program, false));
-        }
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
 
-        Ok(Instruction {
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
             accounts,
             data,
-        })
+        }))
     }
 
     pub fn auto_complete_bounty(
@@ -329,7 +900,8 @@ impl BountyInstruction {
         webhook_authority: &Pubkey,
         bounty_account: &Pubkey,
         reward_account: &Pubkey,
-        token_program: Option<&Pubkey>,
+        token: Option<&BountyToken>,
+        fee_collector: Option<&Pubkey>,
         pr_url: String,
     ) -> Result<Instruction, ProgramError> {
         let data = Self::AutoCompleteBounty {
@@ -337,49 +909,764 @@ impl BountyInstruction {
             pr_url,
         };
         let data = borsh::to_vec(&data)?;
+
+        let (authority_record, _) =
+            crate::processor::Processor::find_webhook_authority_address(program_id, webhook_authority);
+
+        let mut accounts = vec![
+            AccountMeta::new(*webhook_authority, true),
+            AccountMeta::new_readonly(authority_record, false),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*reward_account, false),
+        ];
+
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
+
+        if let Some(fee_collector) = fee_collector {
+            accounts.push(AccountMeta::new(*fee_collector, false));
+        }
         
-        Ok(Instruction {
+        Ok(Self::with_program_config(program_id, Instruction {
             program_id: *program_id,
-            accounts: vec![
-                AccountMeta::new(*webhook_authority, true),
-                AccountMeta::new(*bounty_account, false),
-                AccountMeta::new(*reward_account, false),
-                if let Some(token_program) = token_program {
-                    AccountMeta::new_readonly(*token_program, false)
-                } else {
-                    AccountMeta::new_readonly(*webhook_authority, false)
-                },
-            ],
+            accounts,
             data,
-        })
+        }))
     }
 
-    pub fn add_webhook_authority(
+    pub fn close_bounty(
         program_id: &Pubkey,
-        admin: &Pubkey,
-        authority_account: &Pubkey,
-        authority_to_add: Pubkey,
-        name: String,
+        creator: &Pubkey,
+        bounty_account: &Pubkey,
+        refund_account: &Pubkey,
+        token: Option<&BountyToken>,
     ) -> Result<Instruction, ProgramError> {
-        let data = Self::AddWebhookAuthority {
-            authority: authority_to_add,
-            name,
-        };
-        let data = borsh::to_vec(&data)?;
-        
-        Ok(Instruction {
-            program_id: *program_id,
-            accounts: vec![
-                AccountMeta::new(*admin, true),
-                AccountMeta::new(*authority_account, false),
-                AccountMeta::new_readonly(system_program::id(), false),
-            ],
+        let data = borsh::to_vec(&Self::CloseBounty {
+            bounty_pubkey: *bounty_account,
+        })?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*refund_account, false),
+        ];
+
+        if let Some(token) = token {
+            // Transfer fees withheld in the vault are harvested into the mint before closing it
+            let (token_vault, _) = find_token_vault_address(program_id, bounty_account);
+            accounts.push(AccountMeta::new(token_vault, false));
+            accounts.push(AccountMeta::new_readonly(token.program, false));
+            accounts.push(AccountMeta::new(token.mint, false));
+        } else {
+            let (sol_vault, _) = find_sol_vault_address(program_id, bounty_account);
+            accounts.push(AccountMeta::new(sol_vault, false));
+            accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        }
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
             data,
-        })
+        }))
+    }
+
+    /// `legacy_escrow` is the token account holding the tokens of a token
+    /// bounty that has no token vault yet.
+    pub fn migrate_bounty(
+        program_id: &Pubkey,
+        payer: &Pubkey,
+        bounty_account: &Pubkey,
+        creator: &Pubkey,
+        token: Option<&BountyToken>,
+        legacy_escrow: Option<&Pubkey>,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::MigrateBounty {
+            bounty_pubkey: *bounty_account,
+        })?;
+
+        let (vault, _) = match token {
+            None => find_sol_vault_address(program_id, bounty_account),
+            Some(_) => find_token_vault_address(program_id, bounty_account),
+        };
+
+        let mut accounts = vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*creator, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        if let (Some(token), Some(legacy_escrow)) = (token, legacy_escrow) {
+            let (escrow_authority, _) = find_legacy_escrow_authority_address(program_id, bounty_account);
+            accounts.extend([
+                AccountMeta::new_readonly(token.program, false),
+                AccountMeta::new_readonly(token.mint, false),
+                AccountMeta::new(*legacy_escrow, false),
+                AccountMeta::new_readonly(escrow_authority, false),
+            ]);
+        }
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
     }
 
     /// Unpacks a byte buffer into a BountyInstruction
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
         BorshDeserialize::try_from_slice(input).map_err(|_| ProgramError::InvalidInstructionData)
     }
-} 
\ No newline at end of file
+
+    pub fn migrate_webhook_authority(
+        program_id: &Pubkey,
+        payer: &Pubkey,
+        authority: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::MigrateWebhookAuthority {
+            authority: *authority,
+        })?;
+
+        let (authority_record, _) =
+            crate::processor::Processor::find_webhook_authority_address(program_id, authority);
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*payer, true),
+                AccountMeta::new(authority_record, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            data,
+        }))
+    }
+
+    /// `rent_refund` is the bounty's creator, and `platform` the bounty's
+    /// platform, used to find the allowed host of a new repository URL
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_bounty_metadata(
+        program_id: &Pubkey,
+        creator: &Pubkey,
+        bounty_account: &Pubkey,
+        rent_refund: &Pubkey,
+        platform: ForgePlatform,
+        description: Option<String>,
+        issue_url: Option<String>,
+        repository_url: Option<String>,
+    ) -> Result<Instruction, ProgramError> {
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*rent_refund, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        Self::push_allowed_host(program_id, platform, repository_url.as_deref(), &mut accounts);
+
+        let data = borsh::to_vec(&Self::UpdateBountyMetadata {
+            bounty_pubkey: *bounty_account,
+            description,
+            issue_url,
+            repository_url,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
+    }
+
+    pub fn top_up_bounty(
+        program_id: &Pubkey,
+        creator: &Pubkey,
+        bounty_account: &Pubkey,
+        source_account: &Pubkey,
+        token: Option<&BountyToken>,
+        amount: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::TopUpBounty {
+            bounty_pubkey: *bounty_account,
+            amount,
+        })?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*source_account, false),
+        ];
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
+    }
+
+    pub fn reduce_bounty(
+        program_id: &Pubkey,
+        creator: &Pubkey,
+        bounty_account: &Pubkey,
+        refund_account: &Pubkey,
+        token: Option<&BountyToken>,
+        amount: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::ReduceBounty {
+            bounty_pubkey: *bounty_account,
+            amount,
+        })?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*refund_account, false),
+        ];
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
+    }
+
+    pub fn transfer_bounty_ownership(
+        program_id: &Pubkey,
+        creator: &Pubkey,
+        bounty_account: &Pubkey,
+        new_creator: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::TransferBountyOwnership {
+            bounty_pubkey: *bounty_account,
+            new_creator: *new_creator,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*creator, true),
+                AccountMeta::new(*bounty_account, false),
+            ],
+            data,
+        }))
+    }
+
+    pub fn accept_bounty_ownership(
+        program_id: &Pubkey,
+        new_creator: &Pubkey,
+        bounty_account: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::AcceptBountyOwnership {
+            bounty_pubkey: *bounty_account,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*new_creator, true),
+                AccountMeta::new(*bounty_account, false),
+            ],
+            data,
+        }))
+    }
+
+    pub fn set_bounty_delegate(
+        program_id: &Pubkey,
+        creator: &Pubkey,
+        delegate: &Pubkey,
+        permissions: DelegatePermissions,
+        payer: Option<&Pubkey>,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::SetBountyDelegate {
+            delegate: *delegate,
+            permissions,
+        })?;
+
+        let (org_authority, _) = find_org_authority_address(program_id, creator);
+
+        let mut accounts = vec![
+            AccountMeta::new(*creator, true),
+            AccountMeta::new(org_authority, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        Self::push_payer(program_id, payer, &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
+    }
+
+    /// Lets a delegate sign an instruction built for the creator
+    ///
+    /// Build the instruction with the delegate in place of the creator, then
+    /// pass it through here to append the creator's org authority PDA.
+    pub fn with_org_authority(
+        program_id: &Pubkey,
+        mut instruction: Instruction,
+        creator: &Pubkey,
+    ) -> Instruction {
+        let (org_authority, _) = find_org_authority_address(program_id, creator);
+        // Keep the program config last
+        let index = instruction.accounts.len().saturating_sub(1);
+        instruction.accounts.insert(index, AccountMeta::new_readonly(org_authority, false));
+        instruction
+    }
+
+    pub fn sponsored_lock_bounty(
+        program_id: &Pubkey,
+        relayer: &Pubkey,
+        bounty_account: &Pubkey,
+        claimant: &Pubkey,
+        pr_url: String,
+        expires_at: i64,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::SponsoredLockBounty {
+            bounty_pubkey: *bounty_account,
+            claimant: *claimant,
+            pr_url,
+            expires_at,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*relayer, true),
+                AccountMeta::new(*bounty_account, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            ],
+            data,
+        }))
+    }
+
+    pub fn sponsored_claim_bounty(
+        program_id: &Pubkey,
+        bounty_account: &Pubkey,
+        claimant: &Pubkey,
+        expires_at: i64,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::SponsoredClaimBounty {
+            bounty_pubkey: *bounty_account,
+            claimant: *claimant,
+            expires_at,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*bounty_account, false),
+                AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            ],
+            data,
+        }))
+    }
+
+    pub fn set_program_config(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        paused: bool,
+        disabled_instructions: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::SetProgramConfig {
+            paused,
+            disabled_instructions,
+        })?;
+
+        let (program_config, _) = find_program_config_address(program_id);
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(*admin, true),
+                AccountMeta::new_readonly(find_role_address(program_id, Role::Admin, admin).0, false),
+                AccountMeta::new(program_config, false),
+            ],
+            data,
+        })
+    }
+
+    pub fn initialize_program_config(
+        program_id: &Pubkey,
+        upgrade_authority: &Pubkey,
+        admin: &Pubkey,
+        payer: Option<&Pubkey>,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::InitializeProgramConfig { admin: *admin })?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*upgrade_authority, true),
+            AccountMeta::new_readonly(find_program_data_address(program_id).0, false),
+            AccountMeta::new(find_program_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        Self::push_payer(program_id, payer, &mut accounts);
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    pub fn propose_action(
+        program_id: &Pubkey,
+        proposer: &Pubkey,
+        proposal_id: u64,
+        action: AdminAction,
+        payer: Option<&Pubkey>,
+    ) -> Result<Instruction, ProgramError> {
+        let (role_assignment, _) = find_role_address(program_id, action.required_role(), proposer);
+        let data = borsh::to_vec(&Self::ProposeAction { action })?;
+
+        let (program_config, _) = find_program_config_address(program_id);
+        let (proposal, _) = find_proposal_address(program_id, proposal_id);
+
+        let mut accounts = vec![
+            AccountMeta::new(*proposer, true),
+            AccountMeta::new_readonly(role_assignment, false),
+            AccountMeta::new(program_config, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        Self::push_payer(program_id, payer, &mut accounts);
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    /// `action` is the proposed action, which decides the accounts needed
+    pub fn execute_action(
+        program_id: &Pubkey,
+        executor: &Pubkey,
+        proposal_id: u64,
+        action: &AdminAction,
+        payer: Option<&Pubkey>,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::ExecuteAction { proposal_id })?;
+
+        let (program_config, _) = find_program_config_address(program_id);
+        let (proposal, _) = find_proposal_address(program_id, proposal_id);
+
+        let mut accounts = vec![
+            AccountMeta::new(*executor, true),
+            AccountMeta::new_readonly(find_role_address(program_id, action.required_role(), executor).0, false),
+            AccountMeta::new(program_config, false),
+            AccountMeta::new(proposal, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+
+        let created_account = match action {
+            AdminAction::AddWebhookAuthority { authority, .. } => Some(
+                crate::processor::Processor::find_webhook_authority_address(program_id, authority).0,
+            ),
+            AdminAction::GrantRole { role, holder } => Some(find_role_address(program_id, *role, holder).0),
+            AdminAction::AllowMint { mint, .. } => Some(find_allowed_mint_address(program_id, mint).0),
+            AdminAction::AllowHost { platform, host } => Some(find_allowed_host_address(program_id, *platform, host).0),
+            AdminAction::SetFees { .. } | AdminAction::SetAdmin { .. } => None,
+        };
+        if let Some(created_account) = created_account {
+            accounts.push(AccountMeta::new(created_account, false));
+            Self::push_payer(program_id, payer, &mut accounts);
+        }
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    pub fn cancel_action(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        proposal_id: u64,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::CancelAction { proposal_id })?;
+
+        let (program_config, _) = find_program_config_address(program_id);
+        let (proposal, _) = find_proposal_address(program_id, proposal_id);
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*admin, true),
+                AccountMeta::new_readonly(find_role_address(program_id, Role::Admin, admin).0, false),
+                AccountMeta::new_readonly(program_config, false),
+                AccountMeta::new(proposal, false),
+            ],
+            data,
+        })
+    }
+
+    pub fn grant_role(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        role: Role,
+        holder: &Pubkey,
+        payer: Option<&Pubkey>,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::GrantRole { role, holder: *holder })?;
+
+        let mut accounts = vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(find_role_address(program_id, Role::Admin, admin).0, false),
+            AccountMeta::new_readonly(find_program_config_address(program_id).0, false),
+            AccountMeta::new(find_role_address(program_id, role, holder).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ];
+        Self::push_payer(program_id, payer, &mut accounts);
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        })
+    }
+
+    pub fn revoke_role(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        role: Role,
+        holder: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::RevokeRole { role, holder: *holder })?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*admin, true),
+                AccountMeta::new_readonly(find_role_address(program_id, Role::Admin, admin).0, false),
+                AccountMeta::new_readonly(find_program_config_address(program_id).0, false),
+                AccountMeta::new(find_role_address(program_id, role, holder).0, false),
+            ],
+            data,
+        })
+    }
+
+    pub fn disallow_mint(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::DisallowMint { mint: *mint })?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*admin, true),
+                AccountMeta::new_readonly(find_role_address(program_id, Role::Admin, admin).0, false),
+                AccountMeta::new_readonly(find_program_config_address(program_id).0, false),
+                AccountMeta::new(find_allowed_mint_address(program_id, mint).0, false),
+            ],
+            data,
+        })
+    }
+
+    pub fn disallow_host(
+        program_id: &Pubkey,
+        admin: &Pubkey,
+        platform: ForgePlatform,
+        host: &str,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::DisallowHost { platform, host: host.to_string() })?;
+
+        Ok(Instruction {
+            program_id: *program_id,
+            accounts: vec![
+                AccountMeta::new(*admin, true),
+                AccountMeta::new_readonly(find_role_address(program_id, Role::Admin, admin).0, false),
+                AccountMeta::new_readonly(find_program_config_address(program_id).0, false),
+                AccountMeta::new(find_allowed_host_address(program_id, platform, host).0, false),
+            ],
+            data,
+        })
+    }
+
+    pub fn freeze_bounty(
+        program_id: &Pubkey,
+        moderator: &Pubkey,
+        bounty_account: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::FreezeBounty {
+            bounty_pubkey: *bounty_account,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: Self::moderation_accounts(program_id, moderator, bounty_account),
+            data,
+        }))
+    }
+
+    pub fn unfreeze_bounty(
+        program_id: &Pubkey,
+        moderator: &Pubkey,
+        bounty_account: &Pubkey,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::UnfreezeBounty {
+            bounty_pubkey: *bounty_account,
+        })?;
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts: Self::moderation_accounts(program_id, moderator, bounty_account),
+            data,
+        }))
+    }
+
+    pub fn force_cancel_bounty(
+        program_id: &Pubkey,
+        moderator: &Pubkey,
+        bounty_account: &Pubkey,
+        refund_account: &Pubkey,
+        token: Option<&BountyToken>,
+        reason: u8,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::ForceCancelBounty {
+            bounty_pubkey: *bounty_account,
+            reason,
+        })?;
+
+        let mut accounts = Self::moderation_accounts(program_id, moderator, bounty_account);
+        accounts.push(AccountMeta::new(*refund_account, false));
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
+    }
+
+    /// `payout_account` belongs to the claimant when `award_claimant` is set, to the creator otherwise
+    pub fn resolve_dispute(
+        program_id: &Pubkey,
+        arbiter: &Pubkey,
+        bounty_account: &Pubkey,
+        payout_account: &Pubkey,
+        token: Option<&BountyToken>,
+        award_claimant: bool,
+    ) -> Result<Instruction, ProgramError> {
+        let data = borsh::to_vec(&Self::ResolveDispute {
+            bounty_pubkey: *bounty_account,
+            award_claimant,
+        })?;
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(*arbiter, true),
+            AccountMeta::new_readonly(find_role_address(program_id, Role::Arbiter, arbiter).0, false),
+            AccountMeta::new(*bounty_account, false),
+            AccountMeta::new(*payout_account, false),
+        ];
+        Self::push_vault_accounts(program_id, bounty_account, token, &mut accounts);
+
+        Ok(Self::with_program_config(program_id, Instruction {
+            program_id: *program_id,
+            accounts,
+            data,
+        }))
+    }
+
+    /// Whether the instruction is an admin one, taking the config as a regular account
+    ///
+    /// These are never paused and don't end with the program config PDA.
+    pub fn is_admin(&self) -> bool {
+        matches!(
+            self,
+            Self::SetProgramConfig { .. }
+                | Self::ProposeAction { .. }
+                | Self::ExecuteAction { .. }
+                | Self::CancelAction { .. }
+                | Self::GrantRole { .. }
+                | Self::RevokeRole { .. }
+                | Self::DisallowMint { .. }
+                | Self::DisallowHost { .. }
+                | Self::InitializeProgramConfig { .. }
+        )
+    }
+
+    /// Whether the instruction is a moderator's action on a bounty
+    ///
+    /// These stay available while the program is paused.
+    pub fn is_moderation(&self) -> bool {
+        matches!(
+            self,
+            Self::FreezeBounty { .. } | Self::UnfreezeBounty { .. } | Self::ForceCancelBounty { .. }
+        )
+    }
+
+    /// Whether the instruction withdraws a creator's own funds
+    ///
+    /// These stay available while the program is paused.
+    pub fn is_withdrawal(&self) -> bool {
+        matches!(
+            self,
+            Self::CancelBounty { .. } | Self::ReduceBounty { .. } | Self::CloseBounty { .. }
+        )
+    }
+
+    /// Appends the program config PDA every non-admin instruction ends with
+    fn with_program_config(program_id: &Pubkey, mut instruction: Instruction) -> Instruction {
+        let (program_config, _) = find_program_config_address(program_id);
+        instruction.accounts.push(AccountMeta::new_readonly(program_config, false));
+        instruction
+    }
+
+    /// Moderator, its role assignment and the bounty, which every moderation instruction starts with
+    fn moderation_accounts(program_id: &Pubkey, moderator: &Pubkey, bounty_account: &Pubkey) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(*moderator, true),
+            AccountMeta::new_readonly(find_role_address(program_id, Role::Moderator, moderator).0, false),
+            AccountMeta::new(*bounty_account, false),
+        ]
+    }
+
+    /// Appends the rent payer, or the program id when the signer pays
+    fn push_payer(program_id: &Pubkey, payer: Option<&Pubkey>, accounts: &mut Vec<AccountMeta>) {
+        accounts.push(match payer {
+            Some(payer) => AccountMeta::new(*payer, true),
+            None => AccountMeta::new_readonly(*program_id, false),
+        });
+    }
+
+    /// Appends the allowed host PDA of a self-hosted repository, or the program id otherwise
+    fn push_allowed_host(
+        program_id: &Pubkey,
+        platform: ForgePlatform,
+        repository_url: Option<&str>,
+        accounts: &mut Vec<AccountMeta>,
+    ) {
+        let repository = repository_url.and_then(|url| ForgeUrl::parse_repository(platform, url).ok());
+        accounts.push(match repository {
+            Some(repository) if repository.is_self_hosted() => AccountMeta::new_readonly(
+                find_allowed_host_address(program_id, platform, repository.host).0,
+                false,
+            ),
+            _ => AccountMeta::new_readonly(*program_id, false),
+        });
+    }
+
+    /// Appends the vault, the program that moves its funds and, for token bounties, the mint
+    fn push_vault_accounts(
+        program_id: &Pubkey,
+        bounty_account: &Pubkey,
+        token: Option<&BountyToken>,
+        accounts: &mut Vec<AccountMeta>,
+    ) {
+        if let Some(token) = token {
+            let (token_vault, _) = find_token_vault_address(program_id, bounty_account);
+            accounts.push(AccountMeta::new(token_vault, false));
+            accounts.push(AccountMeta::new_readonly(token.program, false));
+            accounts.push(AccountMeta::new_readonly(token.mint, false));
+        } else {
+            let (sol_vault, _) = find_sol_vault_address(program_id, bounty_account);
+            accounts.push(AccountMeta::new(sol_vault, false));
+            accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use solana_program::hash::hashv;
+
+use crate::{error::BountyError, state::ForgePlatform, url::ForgeUrl};
+
+/// Canonical identifier of an issue: platform, host, owner, repository and number
+///
+/// Components are stored lowercased, so URLs for the same issue that differ
+/// only in case or a trailing slash map to the same identifier. Its hash is
+/// the `issue_hash` a bounty PDA is derived from, and the program recomputes
+/// it on creation, so clients must use `hash` too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueId {
+    pub platform: ForgePlatform,
+    /// Host of the forge instance the issue lives on, e.g. `github.com`
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+impl IssueId {
+    pub fn new(platform: ForgePlatform, host: &str, owner: &str, repo: &str, number: u64) -> Self {
+        Self {
+            platform,
+            host: host.to_ascii_lowercase(),
+            owner: owner.to_ascii_lowercase(),
+            repo: repo.to_ascii_lowercase(),
+            number,
+        }
+    }
+
+    /// Identify the issue an issue URL points to, see `ForgeUrl`
+    pub fn from_issue_url(platform: ForgePlatform, issue_url: &str) -> Result<Self, BountyError> {
+        let url = ForgeUrl::parse_issue(platform, issue_url)?;
+        let number = url.number().ok_or(BountyError::InvalidIssueUrl)?;
+        Ok(Self::new(platform, url.host, url.owner, url.repo, number))
+    }
+
+    /// Canonical string form, `<platform>:<host>/<owner>/<repo>#<number>`
+    pub fn canonical(&self) -> String {
+        format!(
+            "{}:{}/{}/{}#{}",
+            self.platform.name(),
+            self.host,
+            self.owner,
+            self.repo,
+            self.number
+        )
+    }
+
+    /// SHA-256 of the canonical form, used as the bounty's `issue_hash`
+    pub fn hash(&self) -> [u8; 32] {
+        hashv(&[self.canonical().as_bytes()]).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(platform: ForgePlatform, url: &str) -> Result<String, BountyError> {
+        IssueId::from_issue_url(platform, url).map(|id| id.canonical())
+    }
+
+    #[test]
+    fn identifies_issues_on_every_platform() {
+        let cases = [
+            (
+                ForgePlatform::GitHub,
+                "https://github.com/solforge/bounties/issues/1",
+                "github:github.com/solforge/bounties#1",
+            ),
+            (
+                ForgePlatform::GitLab,
+                "https://gitlab.com/solforge/bounties/-/issues/2",
+                "gitlab:gitlab.com/solforge/bounties#2",
+            ),
+            (
+                ForgePlatform::GitLab,
+                "https://gitlab.com/solforge/tools/bounties/-/issues/3",
+                "gitlab:gitlab.com/solforge/tools/bounties#3",
+            ),
+            (
+                ForgePlatform::GitLab,
+                "https://git.example.org/solforge/bounties/-/issues/4",
+                "gitlab:git.example.org/solforge/bounties#4",
+            ),
+            (
+                ForgePlatform::Gitea,
+                "https://gitea.example.org/solforge/bounties/issues/5",
+                "gitea:gitea.example.org/solforge/bounties#5",
+            ),
+            (
+                ForgePlatform::Bitbucket,
+                "https://bitbucket.org/solforge/bounties/issues/6",
+                "bitbucket:bitbucket.org/solforge/bounties#6",
+            ),
+        ];
+
+        for (platform, url, canonical) in cases {
+            assert_eq!(issue(platform, url).as_deref(), Ok(canonical), "{}", url);
+        }
+    }
+
+    #[test]
+    fn normalizes_case_and_trailing_slash() {
+        let expected = IssueId::from_issue_url(ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/1");
+
+        for url in [
+            "https://github.com/solforge/bounties/issues/1/",
+            "HTTPS://GitHub.com/SolForge/Bounties/issues/1",
+            "https://GITHUB.COM/solforge/BOUNTIES/issues/1/",
+        ] {
+            assert_eq!(IssueId::from_issue_url(ForgePlatform::GitHub, url), expected, "{}", url);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_issue_urls() {
+        let cases = [
+            (ForgePlatform::GitHub, "http://github.com/solforge/bounties/issues/1", BountyError::InvalidUrlScheme),
+            (ForgePlatform::GitHub, "github.com/solforge/bounties/issues/1", BountyError::InvalidUrlScheme),
+            (
+                ForgePlatform::GitHub,
+                "https://github.evil.com/solforge/bounties/issues/1",
+                BountyError::UrlHostNotAllowed,
+            ),
+            (ForgePlatform::GitHub, "https://gitlab.com/solforge/bounties/issues/1", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::Gitea, "https://localhost/solforge/bounties/issues/1", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/0", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/1x", BountyError::MalformedUrl),
+            (
+                ForgePlatform::GitHub,
+                "https://github.com/solforge/bounties/issues/1/comments",
+                BountyError::MalformedUrl,
+            ),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/1?page=2", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/1#top", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties", BountyError::InvalidIssueUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/pull/1", BountyError::InvalidIssueUrl),
+        ];
+
+        for (platform, url, error) in cases {
+            assert_eq!(issue(platform, url), Err(error), "{}", url);
+        }
+    }
+
+    #[test]
+    fn hashes_the_canonical_form() {
+        let id = IssueId::new(ForgePlatform::GitHub, "GitHub.com", "SolForge", "Bounties", 1);
+        assert_eq!(id.canonical(), "github:github.com/solforge/bounties#1");
+        // sha256("github:github.com/solforge/bounties#1")
+        assert_eq!(
+            id.hash(),
+            [
+                250, 128, 58, 50, 210, 124, 233, 170, 172, 158, 16, 177, 111, 214, 169, 38, 215, 181, 27, 247, 29,
+                53, 150, 105, 82, 170, 248, 241, 38, 240, 146, 11,
+            ]
+        );
+
+        // Different platforms on the same path are different issues
+        let gitea = IssueId::new(ForgePlatform::Gitea, "github.com", "solforge", "bounties", 1);
+        assert_ne!(gitea.hash(), id.hash());
+    }
+}
@@ -8,10 +8,14 @@ use solana_program::{
 };
 
 // Make modules public so they can be used by external crates
+pub mod authorization;
 pub mod error;
 pub mod instruction;
+pub mod issue;
+pub mod migration;
 pub mod processor;
 pub mod state;
+pub mod url;
 
 use crate::instruction::BountyInstruction;
 
@@ -31,13 +35,16 @@ pub fn process_instruction(
     let instruction = BountyInstruction::try_from_slice(instruction_data)
         .map_err(|_| ProgramError::InvalidInstructionData)?;
 
+    // Every instruction is subject to the pause switch and circuit breaker
+    let (accounts, config) = processor::Processor::check_program_config(program_id, accounts, &instruction, instruction_data[0])?;
+
     // Route to the appropriate processor
     match instruction {
-        BountyInstruction::CreateSolBounty { amount, description, issue_hash, issue_url, repository_url, deadline } => {
-            processor::Processor::process_create_sol_bounty(program_id, accounts, amount, description, issue_hash, issue_url, repository_url, deadline)
+        BountyInstruction::CreateSolBounty { amount, description, issue_hash, issue_url, repository_url, deadline, platform } => {
+            processor::Processor::process_create_sol_bounty(program_id, accounts, amount, description, issue_hash, issue_url, repository_url, deadline, platform, config.as_ref())
         }
-        BountyInstruction::CreateTokenBounty { amount, description, issue_hash, issue_url, repository_url, deadline, token_mint } => {
-            processor::Processor::process_create_token_bounty(program_id, accounts, amount, description, issue_hash, issue_url, repository_url, deadline, token_mint)
+        BountyInstruction::CreateTokenBounty { amount, description, issue_hash, issue_url, repository_url, deadline, token_mint, platform } => {
+            processor::Processor::process_create_token_bounty(program_id, accounts, amount, description, issue_hash, issue_url, repository_url, deadline, token_mint, platform, config.as_ref())
         }
         BountyInstruction::LockBounty { bounty_pubkey, pr_url } => {
             processor::Processor::process_lock_bounty(program_id, accounts, bounty_pubkey, pr_url)
@@ -57,6 +64,78 @@ pub fn process_instruction(
         BountyInstruction::AddWebhookAuthority { authority, name } => {
             processor::Processor::process_add_webhook_authority(program_id, accounts, authority, name)
         }
+        BountyInstruction::CloseBounty { bounty_pubkey } => {
+            processor::Processor::process_close_bounty(program_id, accounts, bounty_pubkey)
+        }
+        BountyInstruction::MigrateBounty { bounty_pubkey } => {
+            processor::Processor::process_migrate_bounty(program_id, accounts, bounty_pubkey)
+        }
+        BountyInstruction::MigrateWebhookAuthority { authority } => {
+            processor::Processor::process_migrate_webhook_authority(program_id, accounts, authority)
+        }
+        BountyInstruction::UpdateBountyMetadata { bounty_pubkey, description, issue_url, repository_url } => {
+            processor::Processor::process_update_bounty_metadata(program_id, accounts, bounty_pubkey, description, issue_url, repository_url)
+        }
+        BountyInstruction::TopUpBounty { bounty_pubkey, amount } => {
+            processor::Processor::process_top_up_bounty(program_id, accounts, bounty_pubkey, amount)
+        }
+        BountyInstruction::ReduceBounty { bounty_pubkey, amount } => {
+            processor::Processor::process_reduce_bounty(program_id, accounts, bounty_pubkey, amount)
+        }
+        BountyInstruction::TransferBountyOwnership { bounty_pubkey, new_creator } => {
+            processor::Processor::process_transfer_bounty_ownership(program_id, accounts, bounty_pubkey, new_creator)
+        }
+        BountyInstruction::AcceptBountyOwnership { bounty_pubkey } => {
+            processor::Processor::process_accept_bounty_ownership(program_id, accounts, bounty_pubkey)
+        }
+        BountyInstruction::SetBountyDelegate { delegate, permissions } => {
+            processor::Processor::process_set_bounty_delegate(program_id, accounts, delegate, permissions)
+        }
+        BountyInstruction::SponsoredLockBounty { bounty_pubkey, claimant, pr_url, expires_at } => {
+            processor::Processor::process_sponsored_lock_bounty(program_id, accounts, bounty_pubkey, claimant, pr_url, expires_at)
+        }
+        BountyInstruction::SponsoredClaimBounty { bounty_pubkey, claimant, expires_at } => {
+            processor::Processor::process_sponsored_claim_bounty(program_id, accounts, bounty_pubkey, claimant, expires_at)
+        }
+        BountyInstruction::SetProgramConfig { paused, disabled_instructions } => {
+            processor::Processor::process_set_program_config(program_id, accounts, paused, disabled_instructions)
+        }
+        BountyInstruction::ProposeAction { action } => {
+            processor::Processor::process_propose_action(program_id, accounts, action)
+        }
+        BountyInstruction::ExecuteAction { proposal_id } => {
+            processor::Processor::process_execute_action(program_id, accounts, proposal_id)
+        }
+        BountyInstruction::CancelAction { proposal_id } => {
+            processor::Processor::process_cancel_action(program_id, accounts, proposal_id)
+        }
+        BountyInstruction::GrantRole { role, holder } => {
+            processor::Processor::process_grant_role(program_id, accounts, role, holder)
+        }
+        BountyInstruction::RevokeRole { role, holder } => {
+            processor::Processor::process_revoke_role(program_id, accounts, role, holder)
+        }
+        BountyInstruction::DisallowMint { mint } => {
+            processor::Processor::process_disallow_mint(program_id, accounts, mint)
+        }
+        BountyInstruction::DisallowHost { platform, host } => {
+            processor::Processor::process_disallow_host(program_id, accounts, platform, host)
+        }
+        BountyInstruction::FreezeBounty { bounty_pubkey } => {
+            processor::Processor::process_freeze_bounty(program_id, accounts, bounty_pubkey, config.as_ref())
+        }
+        BountyInstruction::UnfreezeBounty { bounty_pubkey } => {
+            processor::Processor::process_unfreeze_bounty(program_id, accounts, bounty_pubkey, config.as_ref())
+        }
+        BountyInstruction::ForceCancelBounty { bounty_pubkey, reason } => {
+            processor::Processor::process_force_cancel_bounty(program_id, accounts, bounty_pubkey, reason, config.as_ref())
+        }
+        BountyInstruction::InitializeProgramConfig { admin } => {
+            processor::Processor::process_initialize_program_config(program_id, accounts, admin)
+        }
+        BountyInstruction::ResolveDispute { bounty_pubkey, award_claimant } => {
+            processor::Processor::process_resolve_dispute(program_id, accounts, bounty_pubkey, award_claimant, config.as_ref())
+        }
     }
 }
 
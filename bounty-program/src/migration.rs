@@ -0,0 +1,253 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::BountyError,
+    processor::WebhookAuthority,
+    state::{
+        hash_repository_url, AccountState, AdminProposal, AllowedHost, AllowedMint, Bounty, BountyHeader,
+        BountyStatus, ForgePlatform, OrgAuthority, ProgramConfig, RoleAssignment, ACCOUNT_HEADER_LEN,
+    },
+};
+
+/// Bounty field layout of versions 0 and 1
+///
+/// Version 0 accounts start directly with these fields, without a header.
+/// SOL bounties created with that layout also escrow their amount in the
+/// bounty account itself. Version 1 stores the same fields behind the header.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct BountyV0 {
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub description: String,
+    pub issue_hash: [u8; 32],
+    pub issue_url: String,
+    pub repository_url: String,
+    pub deadline: i64,
+    pub state: BountyStatus,
+    pub token_mint: Option<Pubkey>,
+    pub claimant: Option<Pubkey>,
+    pub pr_url: Option<String>,
+    pub locked_at: Option<i64>,
+    pub claimed_at: Option<i64>,
+    pub completed_at: Option<i64>,
+    pub created_at: i64,
+    pub fee_collector: Option<Pubkey>,
+    pub fee_percentage: u8,
+}
+
+impl From<BountyV0> for Bounty {
+    fn from(legacy: BountyV0) -> Self {
+        Self {
+            header: BountyHeader {
+                state: legacy.state,
+                creator: legacy.creator,
+                token_mint: legacy.token_mint,
+                claimant: legacy.claimant,
+                deadline: legacy.deadline,
+                repository_hash: hash_repository_url(&legacy.repository_url),
+                issue_hash: legacy.issue_hash,
+                amount: legacy.amount,
+                created_at: legacy.created_at,
+                locked_at: legacy.locked_at,
+                claimed_at: legacy.claimed_at,
+                completed_at: legacy.completed_at,
+                fee_collector: legacy.fee_collector,
+                fee_percentage: legacy.fee_percentage,
+                bump: 0,
+                vault_bump: 0,
+                platform: ForgePlatform::GitHub,
+                seed_creator: legacy.creator,
+                pending_creator: None,
+                unfrozen_state: BountyStatus::Available,
+                moderation_reason: 0,
+                legacy_address: false,
+            },
+            description: legacy.description,
+            issue_url: legacy.issue_url,
+            repository_url: legacy.repository_url,
+            pr_url: legacy.pr_url,
+        }
+    }
+}
+
+/// Versions 2 to 6 use the current layout with fewer fixed fields
+///
+/// Fields these versions lack are defaulted, see `BountyHeader::deserialize_version`.
+fn decode_bounty(data: &mut &[u8], version: u8) -> std::io::Result<Bounty> {
+    Ok(Bounty {
+        header: BountyHeader::deserialize_version(data, version)?,
+        description: String::deserialize(data)?,
+        issue_url: String::deserialize(data)?,
+        repository_url: String::deserialize(data)?,
+        pr_url: Option::<String>::deserialize(data)?,
+    })
+}
+
+/// Decode a bounty stored in any supported layout
+///
+/// Returns `None` when the account already uses the current layout, so
+/// callers can treat migration of an up-to-date account as a no-op.
+pub fn upgrade_bounty(data: &[u8]) -> Result<Option<Bounty>, ProgramError> {
+    if data.len() >= ACCOUNT_HEADER_LEN && data[..8] == Bounty::DISCRIMINATOR {
+        return match data[8] {
+            Bounty::VERSION => Ok(None),
+            1 => {
+                let mut data = &data[ACCOUNT_HEADER_LEN..];
+                let legacy = BountyV0::deserialize(&mut data)
+                    .map_err(|_| ProgramError::InvalidAccountData)?;
+                Ok(Some(legacy.into()))
+            }
+            version @ (2..=6) => {
+                let mut data = &data[ACCOUNT_HEADER_LEN..];
+                Ok(Some(decode_bounty(&mut data, version).map_err(|_| ProgramError::InvalidAccountData)?))
+            }
+            _ => Err(BountyError::UnsupportedAccountVersion.into()),
+        };
+    }
+
+    // Other account types are never bounties
+    let other_discriminators = [
+        WebhookAuthority::DISCRIMINATOR,
+        OrgAuthority::DISCRIMINATOR,
+        ProgramConfig::DISCRIMINATOR,
+        AdminProposal::DISCRIMINATOR,
+        RoleAssignment::DISCRIMINATOR,
+        AllowedMint::DISCRIMINATOR,
+        AllowedHost::DISCRIMINATOR,
+    ];
+    if data.len() >= ACCOUNT_HEADER_LEN && other_discriminators.iter().any(|d| data[..8] == *d) {
+        return Err(BountyError::InvalidAccountDiscriminator.into());
+    }
+
+    // Headerless accounts can only be version 0 bounties
+    let mut data = data;
+    let legacy = BountyV0::deserialize(&mut data).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(Some(legacy.into()))
+}
+
+/// Webhook authority layout of versions 0 and 1, before the PDA bump was stored
+///
+/// Version 0 accounts start directly with these fields, without a header,
+/// and were allocated with exactly `LEN` bytes. Version 1 stores the same
+/// fields behind the header.
+#[derive(BorshDeserialize)]
+pub struct WebhookAuthorityV0 {
+    pub authority: Pubkey,
+    pub is_active: bool,
+    pub added_at: i64,
+    pub name: String,
+}
+
+impl WebhookAuthorityV0 {
+    /// Size of version 0 accounts, with 64 bytes reserved for the name
+    pub const LEN: usize = 32 + 1 + 8 + 64;
+}
+
+impl From<WebhookAuthorityV0> for WebhookAuthority {
+    fn from(legacy: WebhookAuthorityV0) -> Self {
+        Self {
+            authority: legacy.authority,
+            bump: 0,
+            is_active: legacy.is_active,
+            added_at: legacy.added_at,
+            name: legacy.name,
+        }
+    }
+}
+
+/// Decode a webhook authority stored in any supported layout
+///
+/// Returns `None` when the account already uses the current layout. The
+/// bump of an upgraded record is left at zero for the caller to fill in.
+pub fn upgrade_webhook_authority(data: &[u8]) -> Result<Option<WebhookAuthority>, ProgramError> {
+    if data.len() >= ACCOUNT_HEADER_LEN && data[..8] == WebhookAuthority::DISCRIMINATOR {
+        return match data[8] {
+            WebhookAuthority::VERSION => Ok(None),
+            1 => {
+                let mut data = &data[ACCOUNT_HEADER_LEN..];
+                let legacy = WebhookAuthorityV0::deserialize(&mut data)
+                    .map_err(|_| ProgramError::InvalidAccountData)?;
+                Ok(Some(legacy.into()))
+            }
+            _ => Err(BountyError::UnsupportedAccountVersion.into()),
+        };
+    }
+
+    // Headerless records are version 0, which had a fixed size
+    if data.len() != WebhookAuthorityV0::LEN {
+        return Err(BountyError::InvalidAccountDiscriminator.into());
+    }
+
+    let mut data = data;
+    let legacy = WebhookAuthorityV0::deserialize(&mut data).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(Some(legacy.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh::BorshSerialize;
+
+    use super::*;
+
+    fn legacy_fields(authority: Pubkey) -> Vec<u8> {
+        let mut data = Vec::new();
+        authority.serialize(&mut data).unwrap();
+        true.serialize(&mut data).unwrap();
+        1_700_000_000i64.serialize(&mut data).unwrap();
+        "relayer".to_string().serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn upgrades_headerless_webhook_authority() {
+        let authority = Pubkey::new_unique();
+        let mut data = legacy_fields(authority);
+        data.resize(WebhookAuthorityV0::LEN, 0);
+
+        let record = upgrade_webhook_authority(&data).unwrap().unwrap();
+        assert_eq!(record.authority, authority);
+        assert_eq!(record.bump, 0);
+        assert!(record.is_active);
+        assert_eq!(record.added_at, 1_700_000_000);
+        assert_eq!(record.name, "relayer");
+    }
+
+    #[test]
+    fn upgrades_version_1_webhook_authority() {
+        let authority = Pubkey::new_unique();
+        let mut data = WebhookAuthority::DISCRIMINATOR.to_vec();
+        data.push(1);
+        data.extend(legacy_fields(authority));
+
+        let record = upgrade_webhook_authority(&data).unwrap().unwrap();
+        assert_eq!(record.authority, authority);
+        assert_eq!(record.name, "relayer");
+    }
+
+    #[test]
+    fn leaves_current_webhook_authority_alone() {
+        let record = WebhookAuthority {
+            authority: Pubkey::new_unique(),
+            bump: 254,
+            is_active: true,
+            added_at: 0,
+            name: "relayer".to_string(),
+        };
+        let mut data = vec![0; WebhookAuthority::LEN];
+        record.pack(&mut data).unwrap();
+
+        assert!(upgrade_webhook_authority(&data).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_headerless_data_of_other_sizes() {
+        let mut data = legacy_fields(Pubkey::new_unique());
+        data.resize(WebhookAuthorityV0::LEN + 1, 0);
+
+        assert_eq!(
+            upgrade_webhook_authority(&data).err(),
+            Some(BountyError::InvalidAccountDiscriminator.into())
+        );
+    }
+}
@@ -1,42 +1,66 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     program::{invoke, invoke_signed},
     pubkey::Pubkey,
     system_instruction,
-    sysvar::{clock::Clock, rent::Rent, Sysvar},
+    sysvar::{self, clock::Clock, rent::Rent, Sysvar},
 };
 
-use spl_token::instruction as token_instruction;
+use spl_token_2022::{
+    extension::{
+        transfer_fee::{instruction as transfer_fee_instruction, TransferFeeAmount, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    instruction as token_instruction,
+    state::{Account as TokenAccount, AccountState as TokenAccountState, Mint},
+};
 
 use crate::{
-    error::BountyError,
+    authorization::{ClaimantAction, ClaimantAuthorization},
+    error::{log_and_return_error, BountyError},
     instruction::BountyInstruction,
-    state::{Bounty, BountyStatus},
+    issue::IssueId,
+    url::{check_self_hosted_host, ForgeUrl},
+    state::{
+        hash_host, hash_repository_url, AccountState, AdminAction, AdminProposal, AllowedHost, AllowedMint, Bounty,
+        BountyHeader,
+        BountyStatus, DelegatePermission, DelegatePermissions, ForgePlatform, OrgAuthority, ProgramConfig, Role,
+        RoleAssignment, ACCOUNT_HEADER_LEN,
+    },
 };
 
-use std::str::FromStr;
-
 pub const WEBHOOK_AUTH_SEED: &[u8] = b"webhook_auth";
 
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct WebhookAuthority {
     pub authority: Pubkey,
+    pub bump: u8,
     pub is_active: bool,
     pub added_at: i64,
     pub name: String,
 }
 
+impl AccountState for WebhookAuthority {
+    const DISCRIMINATOR: [u8; 8] = [252, 38, 149, 193, 194, 10, 8, 10];
+    const VERSION: u8 = 2;
+}
+
 impl WebhookAuthority {
-    pub const LEN: usize = 32 + 1 + 8 + 64;
+    /// Longest name that fits, the account reserves 64 bytes for it including its length
+    pub const MAX_NAME_LEN: usize = 60;
+
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 32 + 1 + 1 + 8 + 4 + Self::MAX_NAME_LEN;
     
-    pub fn new(authority: Pubkey, name: String) -> Self {
+    pub fn new(authority: Pubkey, bump: u8, name: String) -> Self {
         let clock = Clock::get().unwrap();
         Self {
             authority,
+            bump,
             is_active: true,
             added_at: clock.unix_timestamp,
             name,
@@ -48,6 +72,15 @@ impl WebhookAuthority {
     }
 }
 
+/// A bounty's vault and the accounts that move funds in and out of it
+struct VaultAccounts<'a, 'b> {
+    vault_info: &'a AccountInfo<'b>,
+    /// System program for SOL bounties, SPL Token or Token-2022 for token bounties
+    program_info: &'a AccountInfo<'b>,
+    /// Token mint, passed after the token program of token bounties
+    mint_info: Option<&'a AccountInfo<'b>>,
+}
+
 pub struct Processor;
 
 impl Processor {
@@ -57,6 +90,7 @@ impl Processor {
         instruction_data: &[u8],
     ) -> ProgramResult {
         let instruction = BountyInstruction::try_from_slice(instruction_data)?;
+        let (accounts, config) = Self::check_program_config(program_id, accounts, &instruction, instruction_data[0])?;
 
         match instruction {
             BountyInstruction::CreateSolBounty {
@@ -66,6 +100,7 @@ impl Processor {
                 issue_url,
                 repository_url,
                 deadline,
+                platform,
             } => {
                 msg!("Instruction: Create SOL Bounty");
                 Self::process_create_sol_bounty(
@@ -77,6 +112,8 @@ impl Processor {
                     issue_url,
                     repository_url,
                     deadline,
+                    platform,
+                    config.as_ref(),
                 )
             }
             BountyInstruction::CreateTokenBounty {
@@ -87,6 +124,7 @@ impl Processor {
                 repository_url,
                 deadline,
                 token_mint,
+                platform,
             } => {
                 msg!("Instruction: Create Token Bounty");
                 Self::process_create_token_bounty(
@@ -99,6 +137,8 @@ impl Processor {
                     repository_url,
                     deadline,
                     token_mint,
+                    platform,
+                    config.as_ref(),
                 )
             }
             BountyInstruction::LockBounty { bounty_pubkey, pr_url } => {
@@ -125,6 +165,114 @@ impl Processor {
                 msg!("Instruction: Add Webhook Authority");
                 Self::process_add_webhook_authority(program_id, accounts, authority, name)
             }
+            BountyInstruction::CloseBounty { bounty_pubkey } => {
+                msg!("Instruction: Close Bounty");
+                Self::process_close_bounty(program_id, accounts, bounty_pubkey)
+            }
+            BountyInstruction::MigrateBounty { bounty_pubkey } => {
+                msg!("Instruction: Migrate Bounty");
+                Self::process_migrate_bounty(program_id, accounts, bounty_pubkey)
+            }
+            BountyInstruction::MigrateWebhookAuthority { authority } => {
+                msg!("Instruction: Migrate Webhook Authority");
+                Self::process_migrate_webhook_authority(program_id, accounts, authority)
+            }
+            BountyInstruction::UpdateBountyMetadata {
+                bounty_pubkey,
+                description,
+                issue_url,
+                repository_url,
+            } => {
+                msg!("Instruction: Update Bounty Metadata");
+                Self::process_update_bounty_metadata(
+                    program_id,
+                    accounts,
+                    bounty_pubkey,
+                    description,
+                    issue_url,
+                    repository_url,
+                )
+            }
+            BountyInstruction::TopUpBounty { bounty_pubkey, amount } => {
+                msg!("Instruction: Top Up Bounty");
+                Self::process_top_up_bounty(program_id, accounts, bounty_pubkey, amount)
+            }
+            BountyInstruction::ReduceBounty { bounty_pubkey, amount } => {
+                msg!("Instruction: Reduce Bounty");
+                Self::process_reduce_bounty(program_id, accounts, bounty_pubkey, amount)
+            }
+            BountyInstruction::TransferBountyOwnership { bounty_pubkey, new_creator } => {
+                msg!("Instruction: Transfer Bounty Ownership");
+                Self::process_transfer_bounty_ownership(program_id, accounts, bounty_pubkey, new_creator)
+            }
+            BountyInstruction::AcceptBountyOwnership { bounty_pubkey } => {
+                msg!("Instruction: Accept Bounty Ownership");
+                Self::process_accept_bounty_ownership(program_id, accounts, bounty_pubkey)
+            }
+            BountyInstruction::SetBountyDelegate { delegate, permissions } => {
+                msg!("Instruction: Set Bounty Delegate");
+                Self::process_set_bounty_delegate(program_id, accounts, delegate, permissions)
+            }
+            BountyInstruction::SponsoredLockBounty { bounty_pubkey, claimant, pr_url, expires_at } => {
+                msg!("Instruction: Sponsored Lock Bounty");
+                Self::process_sponsored_lock_bounty(program_id, accounts, bounty_pubkey, claimant, pr_url, expires_at)
+            }
+            BountyInstruction::SponsoredClaimBounty { bounty_pubkey, claimant, expires_at } => {
+                msg!("Instruction: Sponsored Claim Bounty");
+                Self::process_sponsored_claim_bounty(program_id, accounts, bounty_pubkey, claimant, expires_at)
+            }
+            BountyInstruction::SetProgramConfig { paused, disabled_instructions } => {
+                msg!("Instruction: Set Program Config");
+                Self::process_set_program_config(program_id, accounts, paused, disabled_instructions)
+            }
+            BountyInstruction::ProposeAction { action } => {
+                msg!("Instruction: Propose Action");
+                Self::process_propose_action(program_id, accounts, action)
+            }
+            BountyInstruction::ExecuteAction { proposal_id } => {
+                msg!("Instruction: Execute Action");
+                Self::process_execute_action(program_id, accounts, proposal_id)
+            }
+            BountyInstruction::CancelAction { proposal_id } => {
+                msg!("Instruction: Cancel Action");
+                Self::process_cancel_action(program_id, accounts, proposal_id)
+            }
+            BountyInstruction::GrantRole { role, holder } => {
+                msg!("Instruction: Grant Role");
+                Self::process_grant_role(program_id, accounts, role, holder)
+            }
+            BountyInstruction::RevokeRole { role, holder } => {
+                msg!("Instruction: Revoke Role");
+                Self::process_revoke_role(program_id, accounts, role, holder)
+            }
+            BountyInstruction::DisallowMint { mint } => {
+                msg!("Instruction: Disallow Mint");
+                Self::process_disallow_mint(program_id, accounts, mint)
+            }
+            BountyInstruction::DisallowHost { platform, host } => {
+                msg!("Instruction: Disallow Host");
+                Self::process_disallow_host(program_id, accounts, platform, host)
+            }
+            BountyInstruction::FreezeBounty { bounty_pubkey } => {
+                msg!("Instruction: Freeze Bounty");
+                Self::process_freeze_bounty(program_id, accounts, bounty_pubkey, config.as_ref())
+            }
+            BountyInstruction::UnfreezeBounty { bounty_pubkey } => {
+                msg!("Instruction: Unfreeze Bounty");
+                Self::process_unfreeze_bounty(program_id, accounts, bounty_pubkey, config.as_ref())
+            }
+            BountyInstruction::ForceCancelBounty { bounty_pubkey, reason } => {
+                msg!("Instruction: Force Cancel Bounty");
+                Self::process_force_cancel_bounty(program_id, accounts, bounty_pubkey, reason, config.as_ref())
+            }
+            BountyInstruction::InitializeProgramConfig { admin } => {
+                msg!("Instruction: Initialize Program Config");
+                Self::process_initialize_program_config(program_id, accounts, admin)
+            }
+            BountyInstruction::ResolveDispute { bounty_pubkey, award_claimant } => {
+                msg!("Instruction: Resolve Dispute");
+                Self::process_resolve_dispute(program_id, accounts, bounty_pubkey, award_claimant, config.as_ref())
+            }
         }
     }
 
@@ -137,23 +285,34 @@ impl Processor {
         issue_url: String,
         repository_url: String,
         deadline: i64,
+        platform: ForgePlatform,
+        config: Option<&ProgramConfig>,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let creator_info = next_account_info(account_info_iter)?;
         let bounty_info = next_account_info(account_info_iter)?;
+        let vault_info = next_account_info(account_info_iter)?;
         let system_program_info = next_account_info(account_info_iter)?;
+        let payer_info = Self::next_payer(program_id, account_info_iter, creator_info)?;
+        let allowed_host_info = Self::next_optional_account(program_id, account_info_iter);
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
 
         if !creator_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        let creator = Self::creator_of_new_bounty(program_id, creator_info, org_authority_info)?;
+
+        Self::validate_bounty_urls(platform, &issue_url, &repository_url, &issue_hash)?;
+        Self::check_allowed_host(program_id, platform, &repository_url, allowed_host_info)?;
+
         // Verify bounty account is the correct PDA
         let (expected_bounty_address, bump_seed) = 
-            crate::instruction::find_bounty_address(program_id, &issue_hash, creator_info.key);
+            crate::instruction::find_bounty_address(program_id, &issue_hash, &creator);
         
         if expected_bounty_address != *bounty_info.key {
-            msg!("Error: Bounty account does not match expected PDA");
-            return Err(ProgramError::InvalidArgument);
+            msg!("Error: Bounty account is not the PDA for this issue and creator");
+            return Err(BountyError::NotAPDA.into());
         }
 
         let clock = Clock::get()?;
@@ -162,42 +321,69 @@ impl Processor {
             return Err(BountyError::InvalidDeadline.into());
         }
 
-        let space = Bounty::LEN;
-        let rent = Rent::get()?;
-        let rent_lamports = rent.minimum_balance(space);
-        let total_lamports = rent_lamports.checked_add(amount).ok_or(BountyError::Overflow)?;
+        // Verify the vault is the bounty's SOL vault PDA
+        let (expected_vault_address, vault_bump_seed) =
+            crate::instruction::find_sol_vault_address(program_id, bounty_info.key);
 
-        // Create bounty account using PDA
-        invoke_signed(
-            &system_instruction::create_account(
-                creator_info.key,
-                bounty_info.key,
-                total_lamports,
-                space as u64,
-                program_id,
-            ),
-            &[creator_info.clone(), bounty_info.clone(), system_program_info.clone()],
-            &[&[
-                crate::instruction::BOUNTY_SEED_PREFIX,
-                &issue_hash[..],
-                creator_info.key.as_ref(),
-                &[bump_seed],
-            ]],
-        )?;
+        if expected_vault_address != *vault_info.key {
+            msg!("Error: Vault account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // New bounties keep the fees in effect when they are created
+        let (fee_collector, fee_percentage) = ProgramConfig::fees(config);
 
-        // Create and initialize the bounty
         let bounty = Bounty::new_sol_bounty(
-            *creator_info.key,
+            creator,
             amount,
             description,
             issue_hash,
             &issue_url,
             &repository_url,
             deadline,
-            None,
-            0,
+            fee_collector,
+            fee_percentage,
+            bump_seed,
+            vault_bump_seed,
+            platform,
         );
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+
+        if Self::bounty_already_created(program_id, bounty_info, &bounty)? {
+            return Ok(());
+        }
+
+        // Create bounty account using PDA
+        Self::create_pda_account(
+            payer_info,
+            bounty_info,
+            system_program_info,
+            bounty.account_size(),
+            program_id,
+            &[
+                crate::instruction::BOUNTY_SEED_PREFIX,
+                &issue_hash[..],
+                creator.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+
+        // Keep the vault itself rent exempt, then escrow the bounty amount in it
+        let rent = Rent::get()?;
+        let vault_rent = rent.minimum_balance(0).saturating_sub(vault_info.lamports());
+
+        if vault_rent > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, vault_info.key, vault_rent),
+                &[payer_info.clone(), vault_info.clone(), system_program_info.clone()],
+            )?;
+        }
+
+        invoke(
+            &system_instruction::transfer(creator_info.key, vault_info.key, amount),
+            &[creator_info.clone(), vault_info.clone(), system_program_info.clone()],
+        )?;
+
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
 
         msg!("SOL bounty created for {} lamports", amount);
         msg!("Issue URL: {}", issue_url);
@@ -216,6 +402,8 @@ impl Processor {
         repository_url: String,
         deadline: i64,
         token_mint: Pubkey,
+        platform: ForgePlatform,
+        config: Option<&ProgramConfig>,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let creator_info = next_account_info(account_info_iter)?;
@@ -225,21 +413,39 @@ impl Processor {
         let token_mint_info = next_account_info(account_info_iter)?;
         let token_program_info = next_account_info(account_info_iter)?;
         let system_program_info = next_account_info(account_info_iter)?;
+        let allowed_mint_info = next_account_info(account_info_iter)?;
+        let payer_info = Self::next_payer(program_id, account_info_iter, creator_info)?;
+        let allowed_host_info = Self::next_optional_account(program_id, account_info_iter);
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
 
         // Validate accounts
         if !creator_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        let creator = Self::creator_of_new_bounty(program_id, creator_info, org_authority_info)?;
+
         // Check if the token mint provided matches the expected token mint
         if token_mint_info.key != &token_mint {
             return Err(BountyError::InvalidTokenMint.into());
         }
 
-        // Validate token program
-        if token_program_info.key != &spl_token::id() {
+        let allowed_mint = Self::load_allowed_mint(program_id, allowed_mint_info, &token_mint)?;
+        if amount < allowed_mint.min_amount {
+            msg!("Error: Bounties in this mint need at least {} tokens", allowed_mint.min_amount);
+            return Err(BountyError::InvalidAmount.into());
+        }
+
+        // Validate token program, either SPL Token or Token-2022
+        if !Self::is_token_program(token_program_info.key) {
             return Err(ProgramError::IncorrectProgramId);
         }
+        let vault = VaultAccounts {
+            vault_info: bounty_token_info,
+            program_info: token_program_info,
+            mint_info: Some(token_mint_info),
+        };
+        Self::check_token_mint(&vault, &token_mint)?;
 
         // Check deadline is in the future
         let clock = Clock::get()?;
@@ -247,58 +453,89 @@ impl Processor {
             return Err(BountyError::InvalidDeadline.into());
         }
 
-        // Create bounty account
-        let space = Bounty::LEN;
-        let rent = Rent::get()?;
-        let rent_lamports = rent.minimum_balance(space);
+        Self::validate_bounty_urls(platform, &issue_url, &repository_url, &issue_hash)?;
+        Self::check_allowed_host(program_id, platform, &repository_url, allowed_host_info)?;
 
-        // Create account and allocate space
-        invoke(
-            &system_instruction::create_account(
-                creator_info.key,
-                bounty_info.key,
-                rent_lamports,
-                space as u64,
-                program_id,
-            ),
-            &[creator_info.clone(), bounty_info.clone(), system_program_info.clone()],
-        )?;
+        // Verify bounty account is the correct PDA
+        let (expected_bounty_address, bump_seed) =
+            crate::instruction::find_bounty_address(program_id, &issue_hash, &creator);
 
-        // Transfer tokens from creator to bounty token account
-        invoke(
-            &token_instruction::transfer(
-                token_program_info.key,
-                creator_token_info.key,
-                bounty_token_info.key,
-                creator_info.key,
-                &[creator_info.key],
-                amount,
-            )?,
-            &[
-                creator_token_info.clone(),
-                bounty_token_info.clone(),
-                creator_info.clone(),
-                token_program_info.clone(),
-            ],
-        )?;
+        if expected_bounty_address != *bounty_info.key {
+            msg!("Error: Bounty account is not the PDA for this issue and creator");
+            return Err(BountyError::NotAPDA.into());
+        }
 
-        // Initialize the bounty
-        let bounty = Bounty::new_token_bounty(
-            *creator_info.key,
-            amount,
+        // Verify the vault is the bounty's token vault PDA
+        let (expected_vault_address, vault_bump_seed) =
+            crate::instruction::find_token_vault_address(program_id, bounty_info.key);
+
+        if expected_vault_address != *bounty_token_info.key {
+            msg!("Error: Vault account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // New bounties keep the fees in effect when they are created
+        let (fee_collector, fee_percentage) = allowed_mint.fees(config);
+
+        // Mints with a transfer fee withhold part of the deposit. The bounty
+        // escrows what the vault receives, which a retried creation has to match.
+        let escrowed_amount = amount
+            .checked_sub(Self::transfer_fee(token_mint_info, amount)?)
+            .ok_or(BountyError::Overflow)?;
+
+        let mut bounty = Bounty::new_token_bounty(
+            creator,
+            escrowed_amount,
             description,
             issue_hash,
             &issue_url,
             &repository_url,
             deadline,
             token_mint,
-            None,
-            0,
+            fee_collector,
+            fee_percentage,
+            bump_seed,
+            vault_bump_seed,
+            platform,
         );
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+
+        if Self::bounty_already_created(program_id, bounty_info, &bounty)? {
+            return Ok(());
+        }
+
+        // Create bounty account using PDA
+        Self::create_pda_account(
+            payer_info,
+            bounty_info,
+            system_program_info,
+            bounty.account_size(),
+            program_id,
+            &[
+                crate::instruction::BOUNTY_SEED_PREFIX,
+                &issue_hash[..],
+                creator.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+
+        // Create the vault token account, owned by the bounty account
+        Self::create_token_vault(
+            payer_info,
+            bounty_info,
+            &vault,
+            system_program_info,
+            bounty_info.key,
+            vault_bump_seed,
+        )?;
+
+        // Transfer tokens from the signer's own token account to the vault
+        bounty.header.amount =
+            Self::transfer_to_vault(&bounty.header, &vault, creator_token_info, creator_info, amount)?;
+
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
 
         // Log successful token bounty creation
-        msg!("Token bounty created for {} tokens of mint {}", amount, token_mint);
+        msg!("Token bounty created for {} tokens of mint {}", bounty.header.amount, token_mint);
         msg!("Issue URL: {}", issue_url);
         msg!("Deadline: {}", deadline);
 
@@ -314,11 +551,71 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
         let claimant_info = next_account_info(account_info_iter)?;
         let bounty_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
         if !claimant_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        Self::lock_bounty(
+            program_id,
+            claimant_info.key,
+            claimant_info,
+            bounty_info,
+            system_program_info,
+            bounty_pubkey,
+            pr_url,
+        )
+    }
+
+    pub fn process_sponsored_lock_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        claimant: Pubkey,
+        pr_url: String,
+        expires_at: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let relayer_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let instructions_info = next_account_info(account_info_iter)?;
+
+        if !relayer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let authorization = ClaimantAuthorization {
+            program_id: *program_id,
+            bounty: bounty_pubkey,
+            claimant,
+            action: ClaimantAction::Lock { pr_url: pr_url.clone() },
+            expires_at,
+        };
+        Self::verify_claimant_authorization(instructions_info, &authorization)?;
+
+        Self::lock_bounty(
+            program_id,
+            &claimant,
+            relayer_info,
+            bounty_info,
+            system_program_info,
+            bounty_pubkey,
+            pr_url,
+        )
+    }
+
+    /// Locks a bounty for `claimant`, with `payer_info` paying the rent for the PR URL
+    fn lock_bounty<'a>(
+        program_id: &Pubkey,
+        claimant: &Pubkey,
+        payer_info: &AccountInfo<'a>,
+        bounty_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        bounty_pubkey: Pubkey,
+        pr_url: String,
+    ) -> ProgramResult {
         if bounty_info.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
@@ -327,15 +624,31 @@ impl Processor {
             return Err(ProgramError::InvalidArgument);
         }
 
-        // Validate PR URL is not empty
-        if pr_url.is_empty() {
-            msg!("Error: PR URL cannot be empty");
-            return Err(ProgramError::InvalidArgument);
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+        bounty.lock(*claimant)?;
+
+        // The PR must be a pull request in the bounty's repository
+        let pull_request = ForgeUrl::parse_pull_request(bounty.platform, &pr_url)
+            .map_err(log_and_return_error)?;
+        {
+            let bounty_data = bounty_info.data.borrow();
+            let repository_url = Bounty::unpack_repository_url(&bounty_data)?;
+            let repository = ForgeUrl::parse_repository(bounty.platform, repository_url)
+                .map_err(log_and_return_error)?;
+
+            if !pull_request.same_repository(&repository) {
+                return Err(log_and_return_error(BountyError::PullRequestNotInRepository));
+            }
         }
 
-        let mut bounty = Bounty::try_from_slice(&bounty_info.data.borrow())?;
-        bounty.lock(*claimant_info.key, pr_url.clone())?;
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+        // Grow the account to fit the PR URL, with the payer covering the extra rent
+        let pr_url_offset = Bounty::pr_url_offset(&bounty_info.data.borrow())?;
+        let new_size = pr_url_offset + 1 + 4 + pr_url.len(); // Option tag, length and PR URL
+        Self::resize_account(bounty_info, payer_info, system_program_info, new_size)?;
+
+        let mut bounty_data = bounty_info.data.borrow_mut();
+        bounty.pack(&mut bounty_data)?;
+        Bounty::pack_pr_url(&mut bounty_data, pr_url_offset, &Some(pr_url.clone()))?;
 
         msg!("Bounty locked with PR URL: {}", pr_url);
         Ok(())
@@ -354,6 +667,38 @@ impl Processor {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        Self::claim_bounty(program_id, claimant_info.key, bounty_info, bounty_pubkey)
+    }
+
+    pub fn process_sponsored_claim_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        claimant: Pubkey,
+        expires_at: i64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bounty_info = next_account_info(account_info_iter)?;
+        let instructions_info = next_account_info(account_info_iter)?;
+
+        let authorization = ClaimantAuthorization {
+            program_id: *program_id,
+            bounty: bounty_pubkey,
+            claimant,
+            action: ClaimantAction::Claim,
+            expires_at,
+        };
+        Self::verify_claimant_authorization(instructions_info, &authorization)?;
+
+        Self::claim_bounty(program_id, &claimant, bounty_info, bounty_pubkey)
+    }
+
+    fn claim_bounty(
+        program_id: &Pubkey,
+        claimant: &Pubkey,
+        bounty_info: &AccountInfo,
+        bounty_pubkey: Pubkey,
+    ) -> ProgramResult {
         if bounty_info.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
@@ -362,13 +707,36 @@ impl Processor {
             return Err(ProgramError::InvalidArgument);
         }
 
-        let mut bounty = Bounty::try_from_slice(&bounty_info.data.borrow())?;
-        bounty.claim(claimant_info.key)?;
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+        bounty.claim(claimant)?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
 
         Ok(())
     }
 
+    /// Checks that the previous instruction verifies the claimant's signed authorization
+    fn verify_claimant_authorization(
+        instructions_info: &AccountInfo,
+        authorization: &ClaimantAuthorization,
+    ) -> ProgramResult {
+        if *instructions_info.key != sysvar::instructions::id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if Clock::get()?.unix_timestamp >= authorization.expires_at {
+            return Err(log_and_return_error(BountyError::ClaimantAuthorizationExpired));
+        }
+
+        let current_index = sysvar::instructions::load_current_index_checked(instructions_info)?;
+        let previous_index = current_index
+            .checked_sub(1)
+            .ok_or_else(|| log_and_return_error(BountyError::InvalidClaimantAuthorization))?;
+        let ed25519_instruction =
+            sysvar::instructions::load_instruction_at_checked(previous_index as usize, instructions_info)?;
+
+        authorization.verify(&ed25519_instruction).map_err(log_and_return_error)
+    }
+
     pub fn process_cancel_bounty(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
@@ -378,6 +746,8 @@ impl Processor {
         let creator_info = next_account_info(account_info_iter)?;
         let bounty_info = next_account_info(account_info_iter)?;
         let refund_info = next_account_info(account_info_iter)?;
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
 
         if !creator_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
@@ -391,53 +761,30 @@ impl Processor {
             return Err(ProgramError::InvalidArgument);
         }
 
-        let mut bounty = Bounty::try_from_slice(&bounty_info.data.borrow())?;
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
 
-        if bounty.creator != *creator_info.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Self::check_creator_or_delegate(
+            program_id,
+            &bounty.creator,
+            creator_info,
+            org_authority_info,
+            DelegatePermission::Cancel,
+        )?;
+        Self::check_payout_owner(&bounty, refund_info, &bounty.creator)?;
 
         bounty.cancel()?;
 
-        // For SOL bounties, transfer the funds back to creator
-        if bounty.token_mint.is_none() {
-            **refund_info.lamports.borrow_mut() = refund_info.lamports()
-                .checked_add(bounty.amount)
-                .ok_or(BountyError::Overflow)?;
-            **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
-                .checked_sub(bounty.amount)
-                .ok_or(BountyError::Overflow)?;
-        } else {
-            // For token bounties, transfer tokens back
-            let token_program_info = next_account_info(account_info_iter)?;
-            
-            // Create PDA for token transfers
-            let (pda, _bump_seed) = Pubkey::find_program_address(
-                &[b"bounty", bounty_info.key.as_ref()],
-                program_id,
-            );
-
-            // Transfer tokens back to creator
-            invoke_signed(
-                &token_instruction::transfer(
-                    token_program_info.key,
-                    bounty_info.key,
-                    refund_info.key,
-                    &pda,
-                    &[],
-                    bounty.amount,
-                )?,
-                &[
-                    bounty_info.clone(),
-                    refund_info.clone(),
-                    creator_info.clone(),
-                    token_program_info.clone(),
-                ],
-                &[&[b"bounty", bounty_info.key.as_ref(), &[_bump_seed]]],
-            )?;
-        }
+        // Return the escrowed funds to the creator
+        Self::transfer_from_vault(
+            program_id,
+            &bounty,
+            bounty_info,
+            &vault,
+            refund_info,
+            bounty.amount,
+        )?;
 
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
 
         Ok(())
     }
@@ -451,6 +798,8 @@ impl Processor {
         let creator_info = next_account_info(account_info_iter)?;
         let bounty_info = next_account_info(account_info_iter)?;
         let reward_info = next_account_info(account_info_iter)?;
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
 
         if !creator_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
@@ -464,53 +813,32 @@ impl Processor {
             return Err(ProgramError::InvalidArgument);
         }
 
-        let mut bounty = Bounty::try_from_slice(&bounty_info.data.borrow())?;
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
 
-        if bounty.creator != *creator_info.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Self::check_creator_or_delegate(
+            program_id,
+            &bounty.creator,
+            creator_info,
+            org_authority_info,
+            DelegatePermission::Complete,
+        )?;
+        // The reward belongs to the claimant, whoever signs
+        let claimant = bounty.claimant.ok_or(ProgramError::InvalidAccountData)?;
+        Self::check_payout_owner(&bounty, reward_info, &claimant)?;
 
         bounty.complete()?;
 
-        // For SOL bounties, transfer the funds to claimant
-        if bounty.token_mint.is_none() {
-            **reward_info.lamports.borrow_mut() = reward_info.lamports()
-                .checked_add(bounty.amount)
-                .ok_or(BountyError::Overflow)?;
-            **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
-                .checked_sub(bounty.amount)
-                .ok_or(BountyError::Overflow)?;
-        } else {
-            // For token bounties, transfer tokens to claimant
-            let token_program_info = next_account_info(account_info_iter)?;
-            
-            // Create PDA for token transfers
-            let (pda, _bump_seed) = Pubkey::find_program_address(
-                &[b"bounty", bounty_info.key.as_ref()],
-                program_id,
-            );
-
-            // Transfer tokens to claimant
-            invoke_signed(
-                &token_instruction::transfer(
-                    token_program_info.key,
-                    bounty_info.key,
-                    reward_info.key,
-                    &pda,
-                    &[],
-                    bounty.amount,
-                )?,
-                &[
-                    bounty_info.clone(),
-                    reward_info.clone(),
-                    creator_info.clone(),
-                    token_program_info.clone(),
-                ],
-                &[&[b"bounty", bounty_info.key.as_ref(), &[_bump_seed]]],
-            )?;
-        }
+        // Pay the escrowed funds to the claimant
+        Self::transfer_from_vault(
+            program_id,
+            &bounty,
+            bounty_info,
+            &vault,
+            reward_info,
+            bounty.amount,
+        )?;
 
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
 
         Ok(())
     }
@@ -532,24 +860,29 @@ impl Processor {
             return Err(ProgramError::MissingRequiredSignature);
         }
         
-        // Verify authority account's PDA
-        let (expected_authority_address, _) = Self::find_webhook_authority_address(
-            program_id, 
-            webhook_authority_info.key
-        );
-        
-        if expected_authority_address != *authority_record_info.key {
-            msg!("Authority record account doesn't match PDA");
-            return Err(ProgramError::InvalidArgument);
-        }
-        
         // Verify the authority record is owned by this program
         if authority_record_info.owner != program_id {
             return Err(ProgramError::IncorrectProgramId);
         }
         
         // Deserialize and verify the authority
-        let authority_record = WebhookAuthority::try_from_slice(&authority_record_info.data.borrow())?;
+        let authority_record = WebhookAuthority::unpack(&authority_record_info.data.borrow())?;
+
+        // Verify authority account's PDA using the stored bump
+        let expected_authority_address = Pubkey::create_program_address(
+            &[
+                WEBHOOK_AUTH_SEED,
+                webhook_authority_info.key.as_ref(),
+                &[authority_record.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+        
+        if expected_authority_address != *authority_record_info.key {
+            msg!("Authority record account doesn't match PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
         
         if !authority_record.is_valid() {
             msg!("Webhook authority is not active");
@@ -571,11 +904,15 @@ impl Processor {
         }
 
         // Read and validate the bounty
-        let mut bounty = Bounty::try_from_slice(&bounty_info.data.borrow())?;
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
         
         // Verify the PR URL matches
-        if bounty.pr_url.as_ref() != Some(&pr_url) {
-            msg!("PR URL mismatch: expected {:?}, got {}", bounty.pr_url, pr_url);
+        let bounty_pr_url = {
+            let bounty_data = bounty_info.data.borrow();
+            Bounty::unpack_pr_url(&bounty_data, Bounty::pr_url_offset(&bounty_data)?)?
+        };
+        if bounty_pr_url.as_ref() != Some(&pr_url) {
+            msg!("PR URL mismatch: expected {:?}, got {}", bounty_pr_url, pr_url);
             return Err(ProgramError::InvalidArgument);
         }
         
@@ -592,163 +929,2286 @@ impl Processor {
         };
         
         // Ensure the reward account belongs to the claimant
-        if *reward_account_info.key != claimant {
-            msg!("Reward account doesn't match claimant");
-            return Err(ProgramError::InvalidArgument);
-        }
-        
-        // For token bounties, verify the token program account is provided
-        if bounty.token_mint.is_some() {
-            let token_program_info = next_account_info(account_info_iter)?;
-            if token_program_info.key != &spl_token::id() {
-                return Err(ProgramError::IncorrectProgramId);
-            }
-        }
+        Self::check_payout_owner(&bounty, reward_account_info, &claimant)?;
         
         // Complete the bounty
         bounty.complete()?;
         
         // Process payment with fees
-        if bounty.token_mint.is_none() {
-            // Check if we need to handle fees
-            if bounty.fee_collector.is_some() && bounty.fee_percentage > 0 {
-                let fee_amount = bounty.calculate_fee();
-                let reward_amount = bounty.amount_after_fee();
-                
-                // If fee collector account is provided, transfer the fee
-                if let Some(fee_collector) = bounty.fee_collector {
-                    let fee_account_info = next_account_info(account_info_iter)?;
-                    
-                    // Verify fee account matches the one in the bounty
-                    if fee_account_info.key != &fee_collector {
-                        msg!("Fee collector account doesn't match bounty");
-                        return Err(ProgramError::InvalidArgument);
-                    }
-                    
-                    // Transfer fee to the fee collector
-                    **fee_account_info.lamports.borrow_mut() = fee_account_info.lamports()
-                        .checked_add(fee_amount)
-                        .ok_or(BountyError::Overflow)?;
-                    
-                    msg!("Fee of {} lamports paid to fee collector", fee_amount);
-                }
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+
+        // Check if we need to handle fees
+        if bounty.fee_collector.is_some() && bounty.fee_percentage > 0 {
+            let fee_amount = bounty.calculate_fee();
+            let reward_amount = bounty.amount_after_fee();
+            
+            // If fee collector account is provided, transfer the fee
+            if let Some(fee_collector) = bounty.fee_collector {
+                let fee_account_info = next_account_info(account_info_iter)?;
                 
-                // Transfer reward amount to claimant
-                **reward_account_info.lamports.borrow_mut() = reward_account_info.lamports()
-                    .checked_add(reward_amount)
-                    .ok_or(BountyError::Overflow)?;
+                // Verify fee account belongs to the bounty's fee collector
+                Self::check_payout_owner(&bounty, fee_account_info, &fee_collector)?;
                 
-                // Reduce the bounty account's lamports
-                **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
-                    .checked_sub(bounty.amount)
-                    .ok_or(BountyError::Overflow)?;
+                // Transfer fee to the fee collector
+                Self::transfer_from_vault(
+                    program_id,
+                    &bounty,
+                    bounty_info,
+                    &vault,
+                    fee_account_info,
+                    fee_amount,
+                )?;
                 
-                msg!("Reward of {} lamports paid to claimer", reward_amount);
-            } else {
-                // No fee, transfer the full amount
-                **reward_account_info.lamports.borrow_mut() = reward_account_info.lamports()
-                    .checked_add(bounty.amount)
-                    .ok_or(BountyError::Overflow)?;
-                **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
-                    .checked_sub(bounty.amount)
-                    .ok_or(BountyError::Overflow)?;
+                msg!("Fee of {} paid to fee collector", fee_amount);
             }
-        } else {
-            // Handle token transfers with fees for SPL tokens
-            let _token_program_info = next_account_info(account_info_iter)?;
-            let _bounty_token_account_info = next_account_info(account_info_iter)?;
-            let _claimant_token_account_info = next_account_info(account_info_iter)?;
             
-            // Create PDA for token transfers
-            let (_pda, _bump_seed) = Pubkey::find_program_address(
-                &[b"bounty", bounty_info.key.as_ref()],
+            // Transfer reward amount to claimant
+            Self::transfer_from_vault(
                 program_id,
-            );
+                &bounty,
+                bounty_info,
+                &vault,
+                reward_account_info,
+                reward_amount,
+            )?;
             
-            // Check if we need to handle fees
-            if bounty.fee_collector.is_some() && bounty.fee_percentage > 0 {
-                let fee_amount = bounty.calculate_fee();
-                let reward_amount = bounty.amount_after_fee();
-                
-                // If fee collector account is provided, transfer the fee
-                if let Some(fee_collector) = bounty.fee_collector {
-                    let fee_account_info = next_account_info(account_info_iter)?;
-                    
-                    // Verify fee account matches the one in the bounty
-                    if fee_account_info.key != &fee_collector {
-                        msg!("Fee collector account doesn't match bounty");
-                        return Err(ProgramError::InvalidArgument);
-                    }
-                    
-                    // Transfer fee to the fee collector
-                    **fee_account_info.lamports.borrow_mut() = fee_account_info.lamports()
-                        .checked_add(fee_amount)
-                        .ok_or(BountyError::Overflow)?;
-                    
-                    msg!("Fee of {} lamports paid to fee collector", fee_amount);
-                }
-                
-                // Transfer reward amount to claimant
-                **reward_account_info.lamports.borrow_mut() = reward_account_info.lamports()
-                    .checked_add(reward_amount)
-                    .ok_or(BountyError::Overflow)?;
-                
-                // Reduce the bounty account's lamports
-                **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
-                    .checked_sub(bounty.amount)
-                    .ok_or(BountyError::Overflow)?;
-                
-                msg!("Reward of {} lamports paid to claimer", reward_amount);
-            } else {
-                // No fee, transfer the full amount
-                **reward_account_info.lamports.borrow_mut() = reward_account_info.lamports()
-                    .checked_add(bounty.amount)
-                    .ok_or(BountyError::Overflow)?;
-                **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
-                    .checked_sub(bounty.amount)
-                    .ok_or(BountyError::Overflow)?;
-            }
+            msg!("Reward of {} paid to claimer", reward_amount);
+        } else {
+            // No fee, transfer the full amount
+            Self::transfer_from_vault(
+                program_id,
+                &bounty,
+                bounty_info,
+                &vault,
+                reward_account_info,
+                bounty.amount,
+            )?;
         }
         
         // Update bounty state
-        bounty.serialize(&mut *bounty_info.data.borrow_mut())?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
         
         msg!("Auto-completed bounty for PR: {}", pr_url);
         Ok(())
     }
 
-    pub fn find_webhook_authority_address(
+    pub fn process_close_bounty(
         program_id: &Pubkey,
-        authority: &Pubkey,
-    ) -> (Pubkey, u8) {
-        Pubkey::find_program_address(
-            &[
-                WEBHOOK_AUTH_SEED,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let refund_info = next_account_info(account_info_iter)?;
+
+        if !creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+
+        if bounty.creator != *creator_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let clock = Clock::get()?;
+        bounty.check_closable(clock.unix_timestamp)?;
+
+        Self::check_payout_owner(&bounty, refund_info, &bounty.creator)?;
+
+        // Return whatever is left in the vault, including its rent, to the creator
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+
+        if bounty.token_mint.is_none() {
+            Self::transfer_from_vault(
+                program_id,
+                &bounty,
+                bounty_info,
+                &vault,
+                refund_info,
+                vault.vault_info.lamports(),
+            )?;
+        } else {
+            // For token bounties, close the vault and return its rent to the creator
+            let vault_info = vault.vault_info;
+            let program_info = vault.program_info;
+            Self::check_vault_address(program_id, &bounty, bounty_info, vault_info)?;
+
+            let token_mint = bounty.token_mint.ok_or(ProgramError::InvalidAccountData)?;
+            let (mint_info, _) = Self::check_token_mint(&vault, &token_mint)?;
+            let authority_info = Self::vault_authority(&bounty, bounty_info, vault_info);
+            Self::check_token_account(vault_info, &token_mint, authority_info.key)?;
+
+            // Anyone can send tokens to the vault, and a non-empty token account
+            // can't be closed, so sweep them to the creator first
+            let remaining_amount = Self::token_balance(vault_info)?;
+            if remaining_amount > 0 {
+                Self::transfer_from_vault(
+                    program_id,
+                    &bounty,
+                    bounty_info,
+                    &vault,
+                    refund_info,
+                    remaining_amount,
+                )?;
+            }
+
+            // Transfer fees withheld in the vault block closing it, move them to the mint first
+            let withheld_amount = {
+                let vault_data = vault_info.data.borrow();
+                StateWithExtensions::<TokenAccount>::unpack(&vault_data)?
+                    .get_extension::<TransferFeeAmount>()
+                    .map_or(0, |transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+            };
+            if withheld_amount > 0 {
+                invoke(
+                    &transfer_fee_instruction::harvest_withheld_tokens_to_mint(
+                        program_info.key,
+                        mint_info.key,
+                        &[vault_info.key],
+                    )?,
+                    &[mint_info.clone(), vault_info.clone(), program_info.clone()],
+                )?;
+            }
+
+            invoke_signed(
+                &token_instruction::close_account(
+                    program_info.key,
+                    vault_info.key,
+                    creator_info.key,
+                    authority_info.key,
+                    &[],
+                )?,
+                &[
+                    vault_info.clone(),
+                    creator_info.clone(),
+                    authority_info.clone(),
+                    program_info.clone(),
+                ],
+                &[&bounty.vault_authority_seeds(bounty_info.key)],
+            )?;
+        }
+
+        // Zero the bounty data and move the remaining lamports to the creator
+        let lamports = bounty_info.lamports();
+        **creator_info.lamports.borrow_mut() = creator_info.lamports()
+            .checked_add(lamports)
+            .ok_or(BountyError::Overflow)?;
+        **bounty_info.lamports.borrow_mut() = 0;
+        bounty_info.data.borrow_mut().fill(0);
+
+        msg!("Bounty closed, {} lamports returned to creator", lamports);
+        Ok(())
+    }
+
+    pub fn process_update_bounty_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        description: Option<String>,
+        issue_url: Option<String>,
+        repository_url: Option<String>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let rent_refund_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let allowed_host_info = Self::next_optional_account(program_id, account_info_iter);
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
+
+        if !creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut bounty = Bounty::unpack(&bounty_info.data.borrow())?;
+
+        Self::check_creator_or_delegate(
+            program_id,
+            &bounty.header.creator,
+            creator_info,
+            org_authority_info,
+            DelegatePermission::Extend,
+        )?;
+
+        if *rent_refund_info.key != bounty.header.creator {
+            msg!("Error: Freed rent must go back to the creator {}", bounty.header.creator);
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if bounty.header.state != BountyStatus::Available {
+            msg!("Error: Only available bounties can be edited");
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        if let Some(description) = description {
+            bounty.description = description;
+        }
+        if let Some(issue_url) = issue_url {
+            bounty.issue_url = issue_url;
+        }
+        if let Some(repository_url) = repository_url {
+            // A host removed from the allowlist keeps its bounties, but not new URLs on it
+            Self::check_allowed_host(program_id, bounty.header.platform, &repository_url, allowed_host_info)?;
+            bounty.repository_url = repository_url;
+        }
+
+        // The URLs may change form but must still identify the same issue
+        Self::validate_bounty_urls(
+            bounty.header.platform,
+            &bounty.issue_url,
+            &bounty.repository_url,
+            &bounty.header.issue_hash,
+        )?;
+        bounty.header.repository_hash = hash_repository_url(&bounty.repository_url);
+
+        // The signer covers growth, rent freed by shrinking goes back to the creator
+        let new_size = bounty.account_size();
+        if new_size > bounty_info.data_len() {
+            Self::resize_account(bounty_info, creator_info, system_program_info, new_size)?;
+        } else {
+            Self::resize_account(bounty_info, rent_refund_info, system_program_info, new_size)?;
+        }
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty metadata updated");
+        Ok(())
+    }
+
+    pub fn process_top_up_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let source_info = next_account_info(account_info_iter)?;
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
+
+        if !creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+
+        Self::check_creator_or_delegate(
+            program_id,
+            &bounty.creator,
+            creator_info,
+            org_authority_info,
+            DelegatePermission::Extend,
+        )?;
+
+        if bounty.is_terminal() {
+            msg!("Error: Completed or cancelled bounties can't be topped up");
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        if bounty.state == BountyStatus::Frozen {
+            msg!("Error: Frozen bounties can't be topped up");
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        if amount == 0 {
+            return Err(BountyError::InvalidAmount.into());
+        }
+
+        Self::check_vault_address(program_id, &bounty, bounty_info, vault.vault_info)?;
+
+        // Transfer-fee mints escrow less than `amount`, the bounty records what arrived
+        let received = Self::transfer_to_vault(&bounty, &vault, source_info, creator_info, amount)?;
+
+        let previous_amount = bounty.amount;
+        bounty.amount = bounty.amount.checked_add(received).ok_or(BountyError::Overflow)?;
+
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty amount increased from {} to {}", previous_amount, bounty.amount);
+        Ok(())
+    }
+
+    pub fn process_reduce_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let refund_info = next_account_info(account_info_iter)?;
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+        let org_authority_info = Self::next_optional_account(program_id, account_info_iter);
+
+        if !creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+
+        Self::check_creator_or_delegate(
+            program_id,
+            &bounty.creator,
+            creator_info,
+            org_authority_info,
+            DelegatePermission::Cancel,
+        )?;
+        Self::check_payout_owner(&bounty, refund_info, &bounty.creator)?;
+
+        if bounty.state != BountyStatus::Available {
+            msg!("Error: Only available bounties can be reduced");
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        let previous_amount = bounty.amount;
+        bounty.amount = match bounty.amount.checked_sub(amount) {
+            Some(remaining) if amount != 0 && remaining != 0 => remaining,
+            _ => {
+                msg!("Error: Reduction must be non-zero and leave funds in the bounty, cancel it instead");
+                return Err(BountyError::InvalidAmount.into());
+            }
+        };
+
+        Self::transfer_from_vault(
+            program_id,
+            &bounty,
+            bounty_info,
+            &vault,
+            refund_info,
+            amount,
+        )?;
+
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty amount reduced from {} to {}, {} refunded", previous_amount, bounty.amount, amount);
+        Ok(())
+    }
+
+    pub fn process_transfer_bounty_ownership(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        new_creator: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+
+        if !creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+
+        if bounty.creator != *creator_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        bounty.offer_ownership(new_creator);
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        match bounty.pending_creator {
+            Some(pending_creator) => msg!("Bounty ownership offered to {}", pending_creator),
+            None => msg!("Pending bounty ownership offer withdrawn"),
+        }
+        Ok(())
+    }
+
+    pub fn process_accept_bounty_ownership(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let new_creator_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+
+        if !new_creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut bounty = BountyHeader::unpack(&bounty_info.data.borrow())?;
+        let previous_creator = bounty.creator;
+
+        bounty.accept_ownership(new_creator_info.key)?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty ownership transferred from {} to {}", previous_creator, bounty.creator);
+        Ok(())
+    }
+
+    pub fn process_set_bounty_delegate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        delegate: Pubkey,
+        permissions: DelegatePermissions,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator_info = next_account_info(account_info_iter)?;
+        let org_authority_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let payer_info = Self::next_payer(program_id, account_info_iter, creator_info)?;
+
+        if !creator_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (expected_org_authority, bump_seed) =
+            crate::instruction::find_org_authority_address(program_id, creator_info.key);
+
+        if expected_org_authority != *org_authority_info.key {
+            msg!("Error: Org authority account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut org_authority = if org_authority_info.owner == program_id {
+            OrgAuthority::unpack(&org_authority_info.data.borrow())?
+        } else {
+            Self::create_pda_account(
+                payer_info,
+                org_authority_info,
+                system_program_info,
+                OrgAuthority::LEN,
+                program_id,
+                &[
+                    crate::instruction::ORG_AUTHORITY_SEED_PREFIX,
+                    creator_info.key.as_ref(),
+                    &[bump_seed],
+                ],
+            )?;
+            OrgAuthority::new(*creator_info.key, bump_seed)
+        };
+
+        org_authority
+            .set_delegate(delegate, permissions)
+            .map_err(log_and_return_error)?;
+        org_authority.pack(&mut org_authority_info.data.borrow_mut())?;
+
+        if permissions.is_empty() {
+            msg!("Delegate {} removed", delegate);
+        } else {
+            msg!("Delegate {} set to {:?}", delegate, permissions);
+        }
+        Ok(())
+    }
+
+    pub fn process_set_program_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        paused: bool,
+        disabled_instructions: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+
+        let mut config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, Role::Admin, admin_info)?;
+
+        config.paused = paused;
+        config.disabled_instructions = disabled_instructions;
+        config.pack(&mut config_info.data.borrow_mut())?;
+
+        msg!("Program paused: {}, disabled instructions: {:#x}", paused, disabled_instructions);
+        Ok(())
+    }
+
+    pub fn process_initialize_program_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        admin: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let program_data_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let payer_info = Self::next_payer(program_id, account_info_iter, authority_info)?;
+
+        Self::check_upgrade_authority(program_id, program_data_info, authority_info)?;
+
+        if Self::load_program_config(program_id, config_info)?.is_some() {
+            msg!("Error: Program config already exists");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let (_, bump_seed) = crate::instruction::find_program_config_address(program_id);
+        Self::create_pda_account(
+            payer_info,
+            config_info,
+            system_program_info,
+            ProgramConfig::LEN,
+            program_id,
+            &[crate::instruction::PROGRAM_CONFIG_SEED, &[bump_seed]],
+        )?;
+        ProgramConfig::new(admin, bump_seed).pack(&mut config_info.data.borrow_mut())?;
+
+        msg!("Program config created with root admin {}", admin);
+        Ok(())
+    }
+
+    pub fn process_propose_action(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        action: AdminAction,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let proposer_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let proposal_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let payer_info = Self::next_payer(program_id, account_info_iter, proposer_info)?;
+
+        let mut config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, action.required_role(), proposer_info)?;
+
+        if let AdminAction::AddWebhookAuthority { name, .. } = &action {
+            if name.len() > WebhookAuthority::MAX_NAME_LEN {
+                msg!("Error: Webhook authority name is longer than {} bytes", WebhookAuthority::MAX_NAME_LEN);
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        if let AdminAction::AllowHost { platform, host } = &action {
+            check_self_hosted_host(*platform, host).map_err(log_and_return_error)?;
+        }
+
+        let proposal_id = config.next_proposal_id;
+        let (expected_address, bump_seed) = crate::instruction::find_proposal_address(program_id, proposal_id);
+        if expected_address != *proposal_info.key {
+            msg!("Error: Proposal account does not match the next proposal id {}", proposal_id);
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let clock = Clock::get()?;
+        let proposal = AdminProposal::new(proposal_id, bump_seed, *proposer_info.key, action, clock.unix_timestamp);
+
+        Self::create_pda_account(
+            payer_info,
+            proposal_info,
+            system_program_info,
+            proposal.account_len(),
+            program_id,
+            &[
+                crate::instruction::PROPOSAL_SEED_PREFIX,
+                &proposal_id.to_le_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+        proposal.pack(&mut proposal_info.data.borrow_mut())?;
+
+        config.next_proposal_id = proposal_id.checked_add(1).ok_or(BountyError::Overflow)?;
+        config.pack(&mut config_info.data.borrow_mut())?;
+
+        msg!("Proposal {} executable at {}: {:?}", proposal_id, proposal.executable_at, proposal.action);
+        Ok(())
+    }
+
+    pub fn process_execute_action(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        proposal_id: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let executor_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let proposal_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        let mut config = Self::load_initialized_program_config(program_id, config_info)?;
+        let proposal = Self::load_proposal(program_id, proposal_info, proposal_id)?;
+        Self::require_role(program_id, &config, role_info, proposal.action.required_role(), executor_info)?;
+
+        let clock = Clock::get()?;
+        if clock.unix_timestamp < proposal.executable_at {
+            msg!("Error: Proposal {} can't be executed before {}", proposal_id, proposal.executable_at);
+            return Err(BountyError::TimelockActive.into());
+        }
+
+        match proposal.action {
+            AdminAction::SetFees { fee_collector, fee_percentage } => {
+                config.fee_collector = fee_collector;
+                config.fee_percentage = fee_percentage;
+                msg!("Fees for new bounties: {} basis points", fee_percentage);
+            }
+            AdminAction::SetAdmin { new_admin } => {
+                config.admin = new_admin;
+                msg!("Program admin changed to {}", new_admin);
+            }
+            AdminAction::AddWebhookAuthority { authority, name } => {
+                let authority_account_info = next_account_info(account_info_iter)?;
+                let payer_info = Self::next_payer(program_id, account_info_iter, executor_info)?;
+                Self::add_webhook_authority(
+                    program_id,
+                    authority_account_info,
+                    system_program_info,
+                    payer_info,
+                    authority,
+                    name,
+                )?;
+            }
+            AdminAction::AllowMint { mint, min_amount, fee_percentage } => {
+                let allowed_mint_info = next_account_info(account_info_iter)?;
+                let payer_info = Self::next_payer(program_id, account_info_iter, executor_info)?;
+                Self::allow_mint(
+                    program_id,
+                    allowed_mint_info,
+                    system_program_info,
+                    payer_info,
+                    mint,
+                    min_amount,
+                    fee_percentage,
+                )?;
+            }
+            AdminAction::AllowHost { platform, host } => {
+                let allowed_host_info = next_account_info(account_info_iter)?;
+                let payer_info = Self::next_payer(program_id, account_info_iter, executor_info)?;
+                Self::allow_host(program_id, allowed_host_info, system_program_info, payer_info, platform, host)?;
+            }
+            AdminAction::GrantRole { role, holder } => {
+                let assignment_info = next_account_info(account_info_iter)?;
+                let payer_info = Self::next_payer(program_id, account_info_iter, executor_info)?;
+                Self::grant_role(
+                    program_id,
+                    assignment_info,
+                    system_program_info,
+                    payer_info,
+                    role,
+                    holder,
+                    proposal.proposer,
+                )?;
+            }
+        }
+
+        config.pack(&mut config_info.data.borrow_mut())?;
+        Self::close_program_account(proposal_info, executor_info)?;
+
+        msg!("Proposal {} executed", proposal_id);
+        Ok(())
+    }
+
+    pub fn process_cancel_action(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        proposal_id: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let proposal_info = next_account_info(account_info_iter)?;
+
+        let config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, Role::Admin, admin_info)?;
+
+        Self::load_proposal(program_id, proposal_info, proposal_id)?;
+        Self::close_program_account(proposal_info, admin_info)?;
+
+        msg!("Proposal {} cancelled", proposal_id);
+        Ok(())
+    }
+
+    pub fn process_grant_role(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        role: Role,
+        holder: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let assignment_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let payer_info = Self::next_payer(program_id, account_info_iter, admin_info)?;
+
+        let config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, Role::Admin, admin_info)?;
+
+        if role == Role::Admin {
+            msg!("Error: The admin role is granted through ProposeAction and ExecuteAction");
+            return Err(BountyError::TimelockRequired.into());
+        }
+
+        Self::grant_role(
+            program_id,
+            assignment_info,
+            system_program_info,
+            payer_info,
+            role,
+            holder,
+            *admin_info.key,
+        )
+    }
+
+    pub fn process_revoke_role(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        role: Role,
+        holder: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let assignment_info = next_account_info(account_info_iter)?;
+
+        let config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, Role::Admin, admin_info)?;
+
+        Self::load_role_assignment(program_id, assignment_info, role, &holder)?;
+        Self::close_program_account(assignment_info, admin_info)?;
+
+        msg!("Role {:?} revoked from {}", role, holder);
+        Ok(())
+    }
+
+    pub fn process_disallow_mint(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        mint: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let allowed_mint_info = next_account_info(account_info_iter)?;
+
+        let config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, Role::Admin, admin_info)?;
+
+        Self::load_allowed_mint(program_id, allowed_mint_info, &mint)?;
+        Self::close_program_account(allowed_mint_info, admin_info)?;
+
+        msg!("Mint {} removed from the allowlist", mint);
+        Ok(())
+    }
+
+    pub fn process_disallow_host(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        platform: ForgePlatform,
+        host: String,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let allowed_host_info = next_account_info(account_info_iter)?;
+
+        let config = Self::load_initialized_program_config(program_id, config_info)?;
+        Self::require_role(program_id, &config, role_info, Role::Admin, admin_info)?;
+
+        Self::load_allowed_host(program_id, allowed_host_info, platform, &host)?;
+        Self::close_program_account(allowed_host_info, admin_info)?;
+
+        msg!("Host {} removed from the allowlist", host);
+        Ok(())
+    }
+
+    pub fn process_freeze_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        config: Option<&ProgramConfig>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let moderator_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+
+        let mut bounty = Self::load_bounty_with_role(
+            program_id,
+            config,
+            Role::Moderator,
+            role_info,
+            moderator_info,
+            bounty_info,
+            bounty_pubkey,
+        )?;
+
+        bounty.freeze()?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty frozen by moderator {}", moderator_info.key);
+        Ok(())
+    }
+
+    pub fn process_unfreeze_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        config: Option<&ProgramConfig>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let moderator_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+
+        let mut bounty = Self::load_bounty_with_role(
+            program_id,
+            config,
+            Role::Moderator,
+            role_info,
+            moderator_info,
+            bounty_info,
+            bounty_pubkey,
+        )?;
+
+        bounty.unfreeze()?;
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty unfrozen by moderator {}", moderator_info.key);
+        Ok(())
+    }
+
+    pub fn process_force_cancel_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        reason: u8,
+        config: Option<&ProgramConfig>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let moderator_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let refund_info = next_account_info(account_info_iter)?;
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+
+        let mut bounty = Self::load_bounty_with_role(
+            program_id,
+            config,
+            Role::Moderator,
+            role_info,
+            moderator_info,
+            bounty_info,
+            bounty_pubkey,
+        )?;
+
+        // The refund can only go back to the creator
+        Self::check_payout_owner(&bounty, refund_info, &bounty.creator)?;
+
+        bounty.force_cancel(reason)?;
+
+        Self::transfer_from_vault(
+            program_id,
+            &bounty,
+            bounty_info,
+            &vault,
+            refund_info,
+            bounty.amount,
+        )?;
+
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty cancelled by moderator {} with reason {}", moderator_info.key, reason);
+        Ok(())
+    }
+
+    pub fn process_resolve_dispute(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+        award_claimant: bool,
+        config: Option<&ProgramConfig>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let arbiter_info = next_account_info(account_info_iter)?;
+        let role_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let payout_info = next_account_info(account_info_iter)?;
+        let vault = Self::next_vault_accounts(account_info_iter)?;
+
+        let mut bounty = Self::load_bounty_with_role(
+            program_id,
+            config,
+            Role::Arbiter,
+            role_info,
+            arbiter_info,
+            bounty_info,
+            bounty_pubkey,
+        )?;
+
+        // Funds go to the claimant if awarded, back to the creator otherwise
+        let payee = if award_claimant {
+            bounty.claimant.ok_or(ProgramError::InvalidAccountData)?
+        } else {
+            bounty.creator
+        };
+        Self::check_payout_owner(&bounty, payout_info, &payee)?;
+
+        bounty.resolve_dispute(award_claimant)?;
+
+        Self::transfer_from_vault(
+            program_id,
+            &bounty,
+            bounty_info,
+            &vault,
+            payout_info,
+            bounty.amount,
+        )?;
+
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Dispute resolved by arbiter {} in favor of {}", arbiter_info.key, payee);
+        Ok(())
+    }
+
+    pub fn process_migrate_bounty(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        bounty_pubkey: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let bounty_info = next_account_info(account_info_iter)?;
+        let creator_info = next_account_info(account_info_iter)?;
+        let vault_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut bounty = match crate::migration::upgrade_bounty(&bounty_info.data.borrow())? {
+            Some(bounty) => bounty,
+            None => {
+                msg!("Bounty already uses the current layout");
+                return Ok(());
+            }
+        };
+
+        if bounty.header.creator != *creator_info.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Layouts before version 3 don't store the PDA bumps
+        let (expected_bounty_address, bump_seed) = crate::instruction::find_bounty_address(
+            program_id,
+            &bounty.header.issue_hash,
+            &bounty.header.seed_creator,
+        );
+
+        // The original program created bounties at client-chosen addresses,
+        // only the program can have written one, so it's genuine
+        if expected_bounty_address != *bounty_info.key {
+            msg!("Bounty lives at a legacy address instead of its PDA");
+            bounty.header.legacy_address = true;
+            bounty.header.bump = 0;
+        } else {
+            bounty.header.bump = bump_seed;
+        }
+
+        let (expected_vault_address, vault_bump_seed) = if bounty.header.token_mint.is_none() {
+            crate::instruction::find_sol_vault_address(program_id, bounty_info.key)
+        } else {
+            crate::instruction::find_token_vault_address(program_id, bounty_info.key)
+        };
+
+        bounty.header.vault_bump = vault_bump_seed;
+
+        // Legacy SOL bounties escrow their amount in the bounty account, move it to the vault
+        let rent = Rent::get()?;
+        let escrowed_lamports = bounty_info.lamports()
+            .saturating_sub(rent.minimum_balance(bounty_info.data_len()));
+
+        if bounty.header.token_mint.is_none() && !bounty.header.is_terminal() && escrowed_lamports >= bounty.header.amount {
+            if expected_vault_address != *vault_info.key {
+                msg!("Error: Vault account does not match expected PDA");
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let vault_rent = rent.minimum_balance(0).saturating_sub(vault_info.lamports());
+            if vault_rent > 0 {
+                invoke(
+                    &system_instruction::transfer(payer_info.key, vault_info.key, vault_rent),
+                    &[payer_info.clone(), vault_info.clone(), system_program_info.clone()],
+                )?;
+            }
+
+            **bounty_info.lamports.borrow_mut() = bounty_info.lamports()
+                .checked_sub(bounty.header.amount)
+                .ok_or(BountyError::Overflow)?;
+            **vault_info.lamports.borrow_mut() = vault_info.lamports()
+                .checked_add(bounty.header.amount)
+                .ok_or(BountyError::Overflow)?;
+
+            msg!("Moved {} escrowed lamports to the SOL vault", bounty.header.amount);
+        }
+
+        // Legacy token bounties escrow their tokens in a token account owned by
+        // the legacy escrow authority, move them to a new token vault. Finished
+        // bounties may have no escrow left, they are migrated without a vault.
+        if let Some(token_mint) = bounty.header.token_mint {
+            let has_escrow = !bounty.header.is_terminal() || !account_info_iter.as_slice().is_empty();
+            if vault_info.data_is_empty() && has_escrow {
+                if expected_vault_address != *vault_info.key {
+                    msg!("Error: Vault account does not match expected PDA");
+                    return Err(ProgramError::InvalidArgument);
+                }
+
+                let vault = VaultAccounts {
+                    vault_info,
+                    program_info: next_account_info(account_info_iter)?,
+                    mint_info: Some(next_account_info(account_info_iter)?),
+                };
+                let escrow_info = next_account_info(account_info_iter)?;
+                let escrow_authority_info = next_account_info(account_info_iter)?;
+                let (mint_info, decimals) = Self::check_token_mint(&vault, &token_mint)?;
+
+                let (escrow_authority, escrow_authority_bump) =
+                    crate::instruction::find_legacy_escrow_authority_address(program_id, bounty_info.key);
+                if escrow_authority != *escrow_authority_info.key {
+                    msg!("Error: Escrow authority does not match expected PDA");
+                    return Err(ProgramError::InvalidArgument);
+                }
+                Self::check_token_account(escrow_info, &token_mint, &escrow_authority)?;
+
+                let authority = *Self::vault_authority(&bounty.header, bounty_info, vault_info).key;
+                Self::create_token_vault(
+                    payer_info,
+                    bounty_info,
+                    &vault,
+                    system_program_info,
+                    &authority,
+                    vault_bump_seed,
+                )?;
+
+                let escrow_authority_seeds: &[&[u8]] = &[
+                    crate::instruction::BOUNTY_SEED_PREFIX,
+                    bounty_info.key.as_ref(),
+                    &[escrow_authority_bump],
+                ];
+                let escrowed_amount = Self::token_balance(escrow_info)?;
+                invoke_signed(
+                    &token_instruction::transfer_checked(
+                        vault.program_info.key,
+                        escrow_info.key,
+                        mint_info.key,
+                        vault_info.key,
+                        escrow_authority_info.key,
+                        &[],
+                        escrowed_amount,
+                        decimals,
+                    )?,
+                    &[
+                        escrow_info.clone(),
+                        mint_info.clone(),
+                        vault_info.clone(),
+                        escrow_authority_info.clone(),
+                        vault.program_info.clone(),
+                    ],
+                    &[escrow_authority_seeds],
+                )?;
+
+                // The empty escrow is of no further use, its rent goes to the creator
+                invoke_signed(
+                    &token_instruction::close_account(
+                        vault.program_info.key,
+                        escrow_info.key,
+                        creator_info.key,
+                        escrow_authority_info.key,
+                        &[],
+                    )?,
+                    &[
+                        escrow_info.clone(),
+                        creator_info.clone(),
+                        escrow_authority_info.clone(),
+                        vault.program_info.clone(),
+                    ],
+                    &[escrow_authority_seeds],
+                )?;
+
+                // Open bounties must still be fully funded
+                let vault_balance = Self::token_balance(vault_info)?;
+                if !bounty.header.is_terminal() && vault_balance < bounty.header.amount {
+                    msg!("Error: Legacy escrow holds {} tokens, the bounty needs {}",
+                        vault_balance, bounty.header.amount);
+                    return Err(BountyError::InsufficientFunds.into());
+                }
+
+                msg!("Moved {} escrowed tokens to the token vault", escrowed_amount);
+            }
+        }
+
+        // The payer covers growth, rent freed by shrinking goes back to the creator
+        let new_size = bounty.account_size();
+        if new_size > bounty_info.data_len() {
+            Self::resize_account(bounty_info, payer_info, system_program_info, new_size)?;
+        } else {
+            Self::resize_account(bounty_info, creator_info, system_program_info, new_size)?;
+        }
+
+        bounty_info.data.borrow_mut().fill(0);
+        bounty.pack(&mut bounty_info.data.borrow_mut())?;
+
+        msg!("Bounty migrated to layout version {}", Bounty::VERSION);
+        Ok(())
+    }
+
+    pub fn process_migrate_webhook_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        authority: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_info = next_account_info(account_info_iter)?;
+        let authority_account_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if authority_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut authority_record =
+            match crate::migration::upgrade_webhook_authority(&authority_account_info.data.borrow())? {
+                Some(authority_record) => authority_record,
+                None => {
+                    msg!("Webhook authority already uses the current layout");
+                    return Ok(());
+                }
+            };
+
+        if authority_record.authority != authority {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (authority_pda, bump_seed) = Self::find_webhook_authority_address(program_id, &authority);
+
+        if authority_pda != *authority_account_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        authority_record.bump = bump_seed;
+
+        Self::resize_account(authority_account_info, payer_info, system_program_info, WebhookAuthority::LEN)?;
+        authority_account_info.data.borrow_mut().fill(0);
+        authority_record.pack(&mut authority_account_info.data.borrow_mut())?;
+
+        msg!("Webhook authority migrated to layout version {}", WebhookAuthority::VERSION);
+        Ok(())
+    }
+
+    /// Validates the URLs of a new bounty and checks its issue hash
+    ///
+    /// The issue must live in the bounty's repository, and `issue_hash` must
+    /// be the hash of the issue identified by `issue_url`.
+    fn validate_bounty_urls(
+        platform: ForgePlatform,
+        issue_url: &str,
+        repository_url: &str,
+        issue_hash: &[u8; 32],
+    ) -> ProgramResult {
+        let repository = ForgeUrl::parse_repository(platform, repository_url).map_err(log_and_return_error)?;
+        let issue = ForgeUrl::parse_issue(platform, issue_url).map_err(log_and_return_error)?;
+
+        if !issue.same_repository(&repository) {
+            return Err(log_and_return_error(BountyError::IssueNotInRepository));
+        }
+
+        let issue_id = IssueId::from_issue_url(platform, issue_url)?;
+        if issue_id.hash() != *issue_hash {
+            msg!("Error: Issue hash does not match {}", issue_id.canonical());
+            return Err(BountyError::InvalidIssueHash.into());
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the bounty account has already been created
+    ///
+    /// Returns `true` when it holds a bounty created with the same parameters,
+    /// so a retried creation succeeds without moving funds again. Any other
+    /// existing account fails with `BountyAlreadyExists`.
+    fn bounty_already_created(
+        program_id: &Pubkey,
+        bounty_info: &AccountInfo,
+        bounty: &Bounty,
+    ) -> Result<bool, ProgramError> {
+        if bounty_info.data_is_empty() {
+            return Ok(false);
+        }
+
+        if bounty_info.owner == program_id {
+            if let Ok(existing) = Bounty::unpack(&bounty_info.data.borrow()) {
+                if existing.has_same_parameters(bounty) {
+                    msg!("Bounty already exists with the same parameters");
+                    return Ok(true);
+                }
+            }
+        }
+
+        msg!("Error: A bounty for this issue already exists");
+        Err(BountyError::BountyAlreadyExists.into())
+    }
+
+    /// Creates an account at a PDA, signing with `signer_seeds`
+    ///
+    /// `create_account` fails when the address already holds lamports, which
+    /// anyone can send to a predictable PDA. Funded addresses are topped up
+    /// to rent exemption, allocated and assigned instead.
+    fn create_pda_account<'a>(
+        payer_info: &AccountInfo<'a>,
+        account_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        space: usize,
+        owner: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let rent = Rent::get()?;
+        let required_lamports = rent.minimum_balance(space);
+
+        if account_info.lamports() == 0 {
+            return invoke_signed(
+                &system_instruction::create_account(
+                    payer_info.key,
+                    account_info.key,
+                    required_lamports,
+                    space as u64,
+                    owner,
+                ),
+                &[payer_info.clone(), account_info.clone(), system_program_info.clone()],
+                &[signer_seeds],
+            );
+        }
+
+        let shortfall = required_lamports.saturating_sub(account_info.lamports());
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, account_info.key, shortfall),
+                &[payer_info.clone(), account_info.clone(), system_program_info.clone()],
+            )?;
+        }
+
+        invoke_signed(
+            &system_instruction::allocate(account_info.key, space as u64),
+            &[account_info.clone(), system_program_info.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(account_info.key, owner),
+            &[account_info.clone(), system_program_info.clone()],
+            &[signer_seeds],
+        )
+    }
+
+    /// Creates a bounty's token vault PDA as a token account owned by `authority`
+    ///
+    /// The vault has room for the extensions the mint requires of its accounts.
+    fn create_token_vault<'a>(
+        payer_info: &AccountInfo<'a>,
+        bounty_info: &AccountInfo<'a>,
+        vault: &VaultAccounts<'_, 'a>,
+        system_program_info: &AccountInfo<'a>,
+        authority: &Pubkey,
+        vault_bump_seed: u8,
+    ) -> ProgramResult {
+        let mint_info = vault.mint_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let vault_len = {
+            let mint_data = mint_info.data.borrow();
+            let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+            let extension_types =
+                ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+            ExtensionType::try_calculate_account_len::<TokenAccount>(&extension_types)?
+        };
+        Self::create_pda_account(
+            payer_info,
+            vault.vault_info,
+            system_program_info,
+            vault_len,
+            vault.program_info.key,
+            &[
+                crate::instruction::TOKEN_VAULT_SEED_PREFIX,
+                bounty_info.key.as_ref(),
+                &[vault_bump_seed],
+            ],
+        )?;
+
+        invoke(
+            &token_instruction::initialize_account3(
+                vault.program_info.key,
+                vault.vault_info.key,
+                mint_info.key,
+                authority,
+            )?,
+            &[vault.vault_info.clone(), mint_info.clone(), vault.program_info.clone()],
+        )
+    }
+
+    /// Resizes a program-owned account, settling the rent difference with the payer
+    ///
+    /// When the account grows the payer tops up the rent, when it shrinks the
+    /// excess rent is returned to the payer.
+    fn resize_account<'a>(
+        account_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        new_size: usize,
+    ) -> ProgramResult {
+        let rent = Rent::get()?;
+        let required_lamports = rent.minimum_balance(new_size);
+        let current_lamports = account_info.lamports();
+
+        if required_lamports > current_lamports {
+            invoke(
+                &system_instruction::transfer(
+                    payer_info.key,
+                    account_info.key,
+                    required_lamports - current_lamports,
+                ),
+                &[payer_info.clone(), account_info.clone(), system_program_info.clone()],
+            )?;
+        } else if current_lamports > required_lamports {
+            let excess = current_lamports - required_lamports;
+            **account_info.lamports.borrow_mut() = required_lamports;
+            **payer_info.lamports.borrow_mut() = payer_info.lamports()
+                .checked_add(excess)
+                .ok_or(BountyError::Overflow)?;
+        }
+
+        account_info.realloc(new_size, false)
+    }
+
+    /// Checks the program config allows an instruction, see `ProgramConfig`
+    ///
+    /// `index` is the instruction's index in `BountyInstruction`. Returns the
+    /// instruction's own accounts, without the trailing program config.
+    pub fn check_program_config<'a, 'b>(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        instruction: &BountyInstruction,
+        index: u8,
+    ) -> Result<(&'a [AccountInfo<'b>], Option<ProgramConfig>), ProgramError> {
+        if instruction.is_admin() {
+            return Ok((accounts, None));
+        }
+
+        let (config_info, accounts) = accounts.split_last().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let config = Self::load_program_config(program_id, config_info)?;
+        if let Some(config) = &config {
+            config
+                .check_instruction(index, instruction.is_withdrawal() || instruction.is_moderation())
+                .map_err(log_and_return_error)?;
+        }
+
+        Ok((accounts, config))
+    }
+
+    /// Checks that `authority_info` signed and is the program's upgrade authority
+    fn check_upgrade_authority(
+        program_id: &Pubkey,
+        program_data_info: &AccountInfo,
+        authority_info: &AccountInfo,
+    ) -> ProgramResult {
+        if !authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (program_data_address, _) = crate::instruction::find_program_data_address(program_id);
+        if program_data_address != *program_data_info.key || *program_data_info.owner != bpf_loader_upgradeable::id() {
+            msg!("Error: Program data account does not belong to this program");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let program_data = program_data_info.data.borrow();
+        let metadata = program_data
+            .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let upgrade_authority = match bincode::deserialize(metadata) {
+            Ok(UpgradeableLoaderState::ProgramData { upgrade_authority_address, .. }) => upgrade_authority_address,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        if upgrade_authority != Some(*authority_info.key) {
+            msg!("Error: {} is not the program's upgrade authority", authority_info.key);
+            return Err(BountyError::Unauthorized.into());
+        }
+
+        Ok(())
+    }
+
+    /// Loads the program config, failing if it hasn't been created yet
+    fn load_initialized_program_config(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+    ) -> Result<ProgramConfig, ProgramError> {
+        Self::load_program_config(program_id, config_info)?.ok_or_else(|| {
+            msg!("Error: Program config has not been created");
+            ProgramError::UninitializedAccount
+        })
+    }
+
+    /// Checks that `holder_info` signed and holds `role`
+    ///
+    /// `role_info` is the holder's assignment PDA for the role, see
+    /// `find_role_address`. The root admin in the program config holds
+    /// `Role::Admin` without one.
+    pub fn require_role(
+        program_id: &Pubkey,
+        config: &ProgramConfig,
+        role_info: &AccountInfo,
+        role: Role,
+        holder_info: &AccountInfo,
+    ) -> ProgramResult {
+        if !holder_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if role == Role::Admin && config.admin == *holder_info.key {
+            return Ok(());
+        }
+
+        if Self::load_role_assignment(program_id, role_info, role, holder_info.key).is_err() {
+            msg!("Error: {} does not hold the {:?} role", holder_info.key, role);
+            return Err(BountyError::Unauthorized.into());
+        }
+
+        Ok(())
+    }
+
+    /// Loads a mint's allowlist entry, failing with `InvalidTokenMint` if it isn't allowed
+    fn load_allowed_mint(
+        program_id: &Pubkey,
+        allowed_mint_info: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<AllowedMint, ProgramError> {
+        if allowed_mint_info.owner != program_id {
+            msg!("Error: Mint {} is not allowed", mint);
+            return Err(BountyError::InvalidTokenMint.into());
+        }
+
+        let allowed_mint = AllowedMint::unpack(&allowed_mint_info.data.borrow())?;
+
+        let expected_address = Pubkey::create_program_address(
+            &[
+                crate::instruction::ALLOWED_MINT_SEED_PREFIX,
+                mint.as_ref(),
+                &[allowed_mint.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if expected_address != *allowed_mint_info.key || allowed_mint.mint != *mint {
+            msg!("Error: Allowed mint account does not match expected PDA");
+            return Err(BountyError::InvalidTokenMint.into());
+        }
+
+        Ok(allowed_mint)
+    }
+
+    /// Creates or updates the allowlist entry of `mint`
+    fn allow_mint<'a>(
+        program_id: &Pubkey,
+        allowed_mint_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        mint: Pubkey,
+        min_amount: u64,
+        fee_percentage: Option<u8>,
+    ) -> ProgramResult {
+        let (expected_address, bump_seed) = crate::instruction::find_allowed_mint_address(program_id, &mint);
+        if expected_address != *allowed_mint_info.key {
+            msg!("Error: Allowed mint account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if allowed_mint_info.owner != program_id {
+            Self::create_pda_account(
+                payer_info,
+                allowed_mint_info,
+                system_program_info,
+                AllowedMint::LEN,
+                program_id,
+                &[
+                    crate::instruction::ALLOWED_MINT_SEED_PREFIX,
+                    mint.as_ref(),
+                    &[bump_seed],
+                ],
+            )?;
+        }
+
+        let allowed_mint = AllowedMint {
+            mint,
+            bump: bump_seed,
+            min_amount,
+            fee_percentage,
+        };
+        allowed_mint.pack(&mut allowed_mint_info.data.borrow_mut())?;
+
+        msg!("Mint {} allowed with a minimum of {} tokens", mint, min_amount);
+        Ok(())
+    }
+
+    /// Checks a self-hosted repository's host is on the allowlist
+    ///
+    /// Repositories on public hosts need no allowlist entry.
+    fn check_allowed_host(
+        program_id: &Pubkey,
+        platform: ForgePlatform,
+        repository_url: &str,
+        allowed_host_info: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        let repository = ForgeUrl::parse_repository(platform, repository_url).map_err(log_and_return_error)?;
+        if !repository.is_self_hosted() {
+            return Ok(());
+        }
+
+        let allowed_host_info = match allowed_host_info {
+            Some(allowed_host_info) => allowed_host_info,
+            None => {
+                msg!("Error: Host {} is not allowed", repository.host);
+                return Err(BountyError::UrlHostNotAllowed.into());
+            }
+        };
+        Self::load_allowed_host(program_id, allowed_host_info, platform, repository.host)?;
+
+        Ok(())
+    }
+
+    /// Loads a host's allowlist entry, failing with `UrlHostNotAllowed` if it isn't allowed
+    fn load_allowed_host(
+        program_id: &Pubkey,
+        allowed_host_info: &AccountInfo,
+        platform: ForgePlatform,
+        host: &str,
+    ) -> Result<AllowedHost, ProgramError> {
+        if allowed_host_info.owner != program_id {
+            msg!("Error: Host {} is not allowed", host);
+            return Err(BountyError::UrlHostNotAllowed.into());
+        }
+
+        let allowed_host = AllowedHost::unpack(&allowed_host_info.data.borrow())?;
+
+        let expected_address = Pubkey::create_program_address(
+            &[
+                crate::instruction::ALLOWED_HOST_SEED_PREFIX,
+                &[platform as u8],
+                &hash_host(host),
+                &[allowed_host.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if expected_address != *allowed_host_info.key
+            || allowed_host.platform != platform
+            || !allowed_host.host.eq_ignore_ascii_case(host)
+        {
+            msg!("Error: Allowed host account does not match expected PDA");
+            return Err(BountyError::UrlHostNotAllowed.into());
+        }
+
+        Ok(allowed_host)
+    }
+
+    /// Creates the allowlist entry of a self-hosted forge instance
+    fn allow_host<'a>(
+        program_id: &Pubkey,
+        allowed_host_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        platform: ForgePlatform,
+        host: String,
+    ) -> ProgramResult {
+        let (expected_address, bump_seed) =
+            crate::instruction::find_allowed_host_address(program_id, platform, &host);
+        if expected_address != *allowed_host_info.key {
+            msg!("Error: Allowed host account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if allowed_host_info.owner != program_id {
+            Self::create_pda_account(
+                payer_info,
+                allowed_host_info,
+                system_program_info,
+                AllowedHost::LEN,
+                program_id,
+                &[
+                    crate::instruction::ALLOWED_HOST_SEED_PREFIX,
+                    &[platform as u8],
+                    &hash_host(&host),
+                    &[bump_seed],
+                ],
+            )?;
+        }
+
+        let allowed_host = AllowedHost {
+            platform,
+            bump: bump_seed,
+            host: host.to_ascii_lowercase(),
+        };
+        allowed_host.pack(&mut allowed_host_info.data.borrow_mut())?;
+
+        msg!("Host {} allowed for {:?}", allowed_host.host, platform);
+        Ok(())
+    }
+
+    /// Loads a bounty for a moderation or arbitration instruction, checking the signer holds `role`
+    fn load_bounty_with_role(
+        program_id: &Pubkey,
+        config: Option<&ProgramConfig>,
+        role: Role,
+        role_info: &AccountInfo,
+        holder_info: &AccountInfo,
+        bounty_info: &AccountInfo,
+        bounty_pubkey: Pubkey,
+    ) -> Result<BountyHeader, ProgramError> {
+        // Roles can only be granted once the config exists
+        let config = config.ok_or(BountyError::Unauthorized)?;
+        Self::require_role(program_id, config, role_info, role, holder_info)?;
+
+        if bounty_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if *bounty_info.key != bounty_pubkey {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        BountyHeader::unpack(&bounty_info.data.borrow())
+    }
+
+    /// Loads the assignment of `role` to `holder`, checking it is the expected PDA
+    fn load_role_assignment(
+        program_id: &Pubkey,
+        assignment_info: &AccountInfo,
+        role: Role,
+        holder: &Pubkey,
+    ) -> Result<RoleAssignment, ProgramError> {
+        if assignment_info.owner != program_id {
+            msg!("Error: Role assignment does not exist");
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let assignment = RoleAssignment::unpack(&assignment_info.data.borrow())?;
+
+        let expected_address = Pubkey::create_program_address(
+            &[
+                crate::instruction::ROLE_SEED_PREFIX,
+                &[role as u8],
+                holder.as_ref(),
+                &[assignment.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if expected_address != *assignment_info.key || assignment.role != role || assignment.holder != *holder {
+            msg!("Error: Role assignment does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(assignment)
+    }
+
+    /// Creates the PDA assigning `role` to `holder`
+    fn grant_role<'a>(
+        program_id: &Pubkey,
+        assignment_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
+        role: Role,
+        holder: Pubkey,
+        granted_by: Pubkey,
+    ) -> ProgramResult {
+        let (expected_address, bump_seed) = crate::instruction::find_role_address(program_id, role, &holder);
+        if expected_address != *assignment_info.key {
+            msg!("Error: Role assignment account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Self::create_pda_account(
+            payer_info,
+            assignment_info,
+            system_program_info,
+            RoleAssignment::LEN,
+            program_id,
+            &[
+                crate::instruction::ROLE_SEED_PREFIX,
+                &[role as u8],
+                holder.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+
+        let clock = Clock::get()?;
+        let assignment = RoleAssignment {
+            role,
+            holder,
+            bump: bump_seed,
+            granted_by,
+            granted_at: clock.unix_timestamp,
+        };
+        assignment.pack(&mut assignment_info.data.borrow_mut())?;
+
+        msg!("Role {:?} granted to {}", role, holder);
+        Ok(())
+    }
+
+    /// Loads a pending proposal, checking it is the PDA for `proposal_id`
+    fn load_proposal(
+        program_id: &Pubkey,
+        proposal_info: &AccountInfo,
+        proposal_id: u64,
+    ) -> Result<AdminProposal, ProgramError> {
+        if proposal_info.owner != program_id {
+            msg!("Error: Proposal does not exist");
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let proposal = AdminProposal::unpack(&proposal_info.data.borrow())?;
+
+        let expected_address = Pubkey::create_program_address(
+            &[
+                crate::instruction::PROPOSAL_SEED_PREFIX,
+                &proposal_id.to_le_bytes(),
+                &[proposal.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if expected_address != *proposal_info.key || proposal.id != proposal_id {
+            msg!("Error: Proposal account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(proposal)
+    }
+
+    /// Closes a program-owned account, sending its rent to `recipient_info`
+    fn close_program_account(account_info: &AccountInfo, recipient_info: &AccountInfo) -> ProgramResult {
+        let lamports = account_info.lamports();
+        **account_info.lamports.borrow_mut() = 0;
+        **recipient_info.lamports.borrow_mut() = recipient_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(BountyError::Overflow)?;
+
+        account_info.data.borrow_mut().fill(0);
+        Ok(())
+    }
+
+    /// Loads the program config, or `None` if it hasn't been created yet
+    fn load_program_config(
+        program_id: &Pubkey,
+        config_info: &AccountInfo,
+    ) -> Result<Option<ProgramConfig>, ProgramError> {
+        if config_info.owner != program_id {
+            let (expected_address, _) = crate::instruction::find_program_config_address(program_id);
+            if expected_address != *config_info.key {
+                msg!("Error: Program config account does not match expected PDA");
+                return Err(ProgramError::InvalidArgument);
+            }
+            return Ok(None);
+        }
+
+        let config = ProgramConfig::unpack(&config_info.data.borrow())?;
+
+        let expected_address = Pubkey::create_program_address(
+            &[crate::instruction::PROGRAM_CONFIG_SEED, &[config.bump]],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if expected_address != *config_info.key {
+            msg!("Error: Program config account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Some(config))
+    }
+
+    /// Returns the next account, or `None` when it is missing or the program id placeholder
+    fn next_optional_account<'a, 'b>(
+        program_id: &Pubkey,
+        account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    ) -> Option<&'a AccountInfo<'b>> {
+        account_info_iter.next().filter(|info| info.key != program_id)
+    }
+
+    /// Returns the optional rent payer, defaulting to `signer_info`
+    ///
+    /// Lets a relayer sponsor the rent of new accounts while the signer still
+    /// authorizes the instruction and funds any escrow.
+    fn next_payer<'a, 'b>(
+        program_id: &Pubkey,
+        account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+        signer_info: &'a AccountInfo<'b>,
+    ) -> Result<&'a AccountInfo<'b>, ProgramError> {
+        let payer_info = Self::next_optional_account(program_id, account_info_iter).unwrap_or(signer_info);
+
+        if !payer_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        Ok(payer_info)
+    }
+
+    /// Loads an org authority, checking it is the PDA of the creator it names
+    fn load_org_authority(
+        program_id: &Pubkey,
+        org_authority_info: &AccountInfo,
+    ) -> Result<OrgAuthority, ProgramError> {
+        if org_authority_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let org_authority = OrgAuthority::unpack(&org_authority_info.data.borrow())?;
+
+        let expected_address = Pubkey::create_program_address(
+            &[
+                crate::instruction::ORG_AUTHORITY_SEED_PREFIX,
+                org_authority.creator.as_ref(),
+                &[org_authority.bump],
+            ],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if expected_address != *org_authority_info.key {
+            msg!("Error: Org authority account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(org_authority)
+    }
+
+    /// Checks that the signer is `creator` or one of its delegates with `permission`
+    ///
+    /// Delegates must pass the creator's org authority.
+    fn check_creator_or_delegate(
+        program_id: &Pubkey,
+        creator: &Pubkey,
+        signer_info: &AccountInfo,
+        org_authority_info: Option<&AccountInfo>,
+        permission: DelegatePermission,
+    ) -> ProgramResult {
+        if signer_info.key == creator {
+            return Ok(());
+        }
+
+        let org_authority_info = org_authority_info.ok_or(ProgramError::InvalidAccountData)?;
+        let org_authority = Self::load_org_authority(program_id, org_authority_info)?;
+
+        if org_authority.creator != *creator {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !org_authority.allows(signer_info.key, permission) {
+            msg!("Error: {} is not a delegate of {} allowed to {:?}", signer_info.key, creator, permission);
+            return Err(BountyError::Unauthorized.into());
+        }
+
+        msg!("Acting as delegate of {}", creator);
+        Ok(())
+    }
+
+    /// Returns the creator of a bounty being created by the signer
+    ///
+    /// That is the signer itself, or the creator whose org authority is passed
+    /// when the signer is a delegate allowed to create bounties.
+    fn creator_of_new_bounty(
+        program_id: &Pubkey,
+        signer_info: &AccountInfo,
+        org_authority_info: Option<&AccountInfo>,
+    ) -> Result<Pubkey, ProgramError> {
+        let org_authority_info = match org_authority_info {
+            Some(org_authority_info) => org_authority_info,
+            None => return Ok(*signer_info.key),
+        };
+
+        let org_authority = Self::load_org_authority(program_id, org_authority_info)?;
+
+        if *signer_info.key != org_authority.creator
+            && !org_authority.allows(signer_info.key, DelegatePermission::Create)
+        {
+            msg!("Error: {} is not a delegate of {} allowed to create bounties", signer_info.key, org_authority.creator);
+            return Err(BountyError::Unauthorized.into());
+        }
+
+        Ok(org_authority.creator)
+    }
+
+    /// Checks that a payout account belongs to `owner`
+    ///
+    /// SOL is paid to the wallet itself, tokens to an initialized token
+    /// account of the bounty's mint owned by the wallet.
+    fn check_payout_owner(
+        bounty: &BountyHeader,
+        account_info: &AccountInfo,
+        owner: &Pubkey,
+    ) -> ProgramResult {
+        match bounty.token_mint {
+            None => {
+                if account_info.key != owner {
+                    msg!("Error: Payout account does not belong to {}", owner);
+                    return Err(ProgramError::InvalidArgument);
+                }
+            }
+            Some(token_mint) => {
+                Self::check_token_account(account_info, &token_mint, owner)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unpacks a token account and checks its mint and owner
+    ///
+    /// The account must be an initialized, unfrozen SPL Token or Token-2022
+    /// account.
+    fn check_token_account(
+        account_info: &AccountInfo,
+        mint: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<TokenAccount, ProgramError> {
+        if !Self::is_token_program(account_info.owner) {
+            msg!("Error: {} is not a token account", account_info.key);
+            return Err(BountyError::InvalidTokenAccount.into());
+        }
+
+        let token_account = StateWithExtensions::<TokenAccount>::unpack(&account_info.data.borrow())
+            .map_err(|_| {
+                msg!("Error: Token account {} is uninitialized", account_info.key);
+                BountyError::InvalidTokenAccount
+            })?
+            .base;
+
+        if token_account.state != TokenAccountState::Initialized {
+            msg!("Error: Token account {} is frozen", account_info.key);
+            return Err(BountyError::InvalidTokenAccount.into());
+        }
+
+        if token_account.mint != *mint {
+            msg!("Error: Token account {} holds mint {}, expected {}",
+                account_info.key, token_account.mint, mint);
+            return Err(BountyError::TokenAccountMintMismatch.into());
+        }
+
+        if token_account.owner != *owner {
+            msg!("Error: Token account {} is owned by {}, expected {}",
+                account_info.key, token_account.owner, owner);
+            return Err(BountyError::TokenAccountOwnerMismatch.into());
+        }
+
+        Ok(token_account)
+    }
+
+    /// Whether `program_id` is the SPL Token or the Token-2022 program
+    fn is_token_program(program_id: &Pubkey) -> bool {
+        program_id == &spl_token::id() || program_id == &spl_token_2022::id()
+    }
+
+    /// Reads a bounty's vault and the program that moves its funds
+    ///
+    /// A token program is followed by the token mint, which
+    /// `transfer_checked` needs.
+    fn next_vault_accounts<'a, 'b>(
+        account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    ) -> Result<VaultAccounts<'a, 'b>, ProgramError> {
+        let vault_info = next_account_info(account_info_iter)?;
+        let program_info = next_account_info(account_info_iter)?;
+        let mint_info = if Self::is_token_program(program_info.key) {
+            Some(next_account_info(account_info_iter)?)
+        } else {
+            None
+        };
+
+        Ok(VaultAccounts { vault_info, program_info, mint_info })
+    }
+
+    /// Checks the token program and mint of a token bounty, returning the mint and its decimals
+    fn check_token_mint<'a, 'b>(
+        vault: &VaultAccounts<'a, 'b>,
+        token_mint: &Pubkey,
+    ) -> Result<(&'a AccountInfo<'b>, u8), ProgramError> {
+        if !Self::is_token_program(vault.program_info.key) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mint_info = vault.mint_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if mint_info.key != token_mint || mint_info.owner != vault.program_info.key {
+            msg!("Error: Mint account is not {} owned by the token program", token_mint);
+            return Err(BountyError::InvalidTokenMint.into());
+        }
+
+        let decimals = StateWithExtensions::<Mint>::unpack(&mint_info.data.borrow())?.base.decimals;
+        Ok((mint_info, decimals))
+    }
+
+    /// Token balance of a token account, excluding any withheld transfer fees
+    fn token_balance(account_info: &AccountInfo) -> Result<u64, ProgramError> {
+        Ok(StateWithExtensions::<TokenAccount>::unpack(&account_info.data.borrow())?.base.amount)
+    }
+
+    /// Transfer fee a Token-2022 mint withholds on a transfer of `amount` this epoch
+    ///
+    /// Zero for mints without the transfer fee extension.
+    fn transfer_fee(mint_info: &AccountInfo, amount: u64) -> Result<u64, ProgramError> {
+        let mint_data = mint_info.data.borrow();
+        let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        match mint.get_extension::<TransferFeeConfig>() {
+            Ok(transfer_fee_config) => transfer_fee_config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or_else(|| BountyError::Overflow.into()),
+            Err(_) => Ok(0),
+        }
+    }
+
+    /// Checks that `vault_info` is the bounty's vault PDA, using the stored vault bump
+    fn check_vault_address(
+        program_id: &Pubkey,
+        bounty: &BountyHeader,
+        bounty_info: &AccountInfo,
+        vault_info: &AccountInfo,
+    ) -> ProgramResult {
+        let seed_prefix = if bounty.token_mint.is_none() {
+            crate::instruction::SOL_VAULT_SEED_PREFIX
+        } else {
+            crate::instruction::TOKEN_VAULT_SEED_PREFIX
+        };
+
+        let vault_address = Pubkey::create_program_address(
+            &[seed_prefix, bounty_info.key.as_ref(), &[bounty.vault_bump]],
+            program_id,
+        )
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+
+        if vault_address != *vault_info.key {
+            msg!("Error: Vault account does not match expected PDA");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(())
+    }
+
+    /// Transfers funds into a bounty's vault, returning the amount the vault received
+    ///
+    /// SOL comes from a signing wallet. Tokens come from a token account owned
+    /// by `authority_info`. Token-2022 mints with a transfer fee withhold part
+    /// of the transfer, so the vault's balance change is what gets escrowed.
+    fn transfer_to_vault<'a>(
+        bounty: &BountyHeader,
+        vault: &VaultAccounts<'_, 'a>,
+        source_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let token_mint = match bounty.token_mint {
+            None => {
+                invoke(
+                    &system_instruction::transfer(source_info.key, vault.vault_info.key, amount),
+                    &[source_info.clone(), vault.vault_info.clone(), vault.program_info.clone()],
+                )?;
+                return Ok(amount);
+            }
+            Some(token_mint) => token_mint,
+        };
+
+        let (mint_info, decimals) = Self::check_token_mint(vault, &token_mint)?;
+        Self::check_token_account(source_info, &token_mint, authority_info.key)?;
+
+        let balance_before = Self::token_balance(vault.vault_info)?;
+        invoke(
+            &token_instruction::transfer_checked(
+                vault.program_info.key,
+                source_info.key,
+                mint_info.key,
+                vault.vault_info.key,
+                authority_info.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[
+                source_info.clone(),
+                mint_info.clone(),
+                vault.vault_info.clone(),
+                authority_info.clone(),
+                vault.program_info.clone(),
+            ],
+        )?;
+        let balance_after = Self::token_balance(vault.vault_info)?;
+
+        balance_after.checked_sub(balance_before).ok_or_else(|| BountyError::Overflow.into())
+    }
+
+    /// Transfers funds out of a bounty's vault
+    ///
+    /// SOL vaults sign with the stored vault bump. Token vaults are owned by
+    /// the bounty account, which signs with the stored bounty bump, except at
+    /// legacy addresses, see `BountyHeader::legacy_address`.
+    fn transfer_from_vault<'a>(
+        program_id: &Pubkey,
+        bounty: &BountyHeader,
+        bounty_info: &AccountInfo<'a>,
+        vault: &VaultAccounts<'_, 'a>,
+        destination_info: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let vault_info = vault.vault_info;
+        let program_info = vault.program_info;
+        Self::check_vault_address(program_id, bounty, bounty_info, vault_info)?;
+
+        let token_mint = match bounty.token_mint {
+            None => {
+                return invoke_signed(
+                    &system_instruction::transfer(vault_info.key, destination_info.key, amount),
+                    &[vault_info.clone(), destination_info.clone(), program_info.clone()],
+                    &[&[
+                        crate::instruction::SOL_VAULT_SEED_PREFIX,
+                        bounty_info.key.as_ref(),
+                        &[bounty.vault_bump],
+                    ]],
+                );
+            }
+            Some(token_mint) => token_mint,
+        };
+
+        let (mint_info, decimals) = Self::check_token_mint(vault, &token_mint)?;
+        let authority_info = Self::vault_authority(bounty, bounty_info, vault_info);
+        Self::check_token_account(vault_info, &token_mint, authority_info.key)?;
+
+        invoke_signed(
+            &token_instruction::transfer_checked(
+                program_info.key,
+                vault_info.key,
+                mint_info.key,
+                destination_info.key,
+                authority_info.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[
+                vault_info.clone(),
+                mint_info.clone(),
+                destination_info.clone(),
+                authority_info.clone(),
+                program_info.clone(),
+            ],
+            &[&bounty.vault_authority_seeds(bounty_info.key)],
+        )
+    }
+
+    /// Owner of a bounty's token vault, signing with `BountyHeader::vault_authority_seeds`
+    fn vault_authority<'a, 'b>(
+        bounty: &BountyHeader,
+        bounty_info: &'a AccountInfo<'b>,
+        vault_info: &'a AccountInfo<'b>,
+    ) -> &'a AccountInfo<'b> {
+        if bounty.legacy_address {
+            vault_info
+        } else {
+            bounty_info
+        }
+    }
+
+    pub fn find_webhook_authority_address(
+        program_id: &Pubkey,
+        authority: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                WEBHOOK_AUTH_SEED,
                 authority.as_ref(),
             ],
             program_id
         )
     }
 
+    /// Webhook authorities are only added through an executed `ProposeAction`
     pub fn process_add_webhook_authority(
+        _program_id: &Pubkey,
+        _accounts: &[AccountInfo],
+        _authority_to_add: Pubkey,
+        _name: String,
+    ) -> ProgramResult {
+        msg!("Error: Webhook authorities must be added through ProposeAction and ExecuteAction");
+        Err(BountyError::TimelockRequired.into())
+    }
+
+    /// Creates the PDA allowing `authority_to_add` to complete bounties through webhooks
+    fn add_webhook_authority<'a>(
         program_id: &Pubkey,
-        accounts: &[AccountInfo],
+        authority_account_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        payer_info: &AccountInfo<'a>,
         authority_to_add: Pubkey,
         name: String,
     ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
-        let authority_account_info = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-        
-        // Hardcoded admin key for bootstrapping (in production, this would be governed by DAO)
-        let admin_key = Pubkey::from_str("Tge7QM2HroSQEBNXTyacb5YVZRJjkRmC8Qh8QvhPuXM").unwrap();
-        
-        // Verify the admin is a signer and the correct key
-        if !admin_info.is_signer || admin_info.key != &admin_key {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-        
         // Calculate the authority PDA
         let (authority_pda, bump_seed) = Self::find_webhook_authority_address(program_id, &authority_to_add);
         
@@ -757,31 +3217,23 @@ impl Processor {
             return Err(ProgramError::InvalidArgument);
         }
         
-        // Calculate account size and rent
-        let space = WebhookAuthority::LEN;
-        let rent = Rent::get()?;
-        let rent_lamports = rent.minimum_balance(space);
-        
         // Create the authority account
-        invoke_signed(
-            &system_instruction::create_account(
-                admin_info.key,
-                authority_account_info.key,
-                rent_lamports,
-                space as u64,
-                program_id,
-            ),
-            &[admin_info.clone(), authority_account_info.clone(), system_program_info.clone()],
-            &[&[
+        Self::create_pda_account(
+            payer_info,
+            authority_account_info,
+            system_program_info,
+            WebhookAuthority::LEN,
+            program_id,
+            &[
                 WEBHOOK_AUTH_SEED,
                 authority_to_add.as_ref(),
                 &[bump_seed],
-            ]],
+            ],
         )?;
         
         // Initialize the authority account
-        let authority = WebhookAuthority::new(authority_to_add, name);
-        authority.serialize(&mut *authority_account_info.data.borrow_mut())?;
+        let authority = WebhookAuthority::new(authority_to_add, bump_seed, name);
+        authority.pack(&mut authority_account_info.data.borrow_mut())?;
         
         msg!("Webhook authority added: {}", authority_to_add);
         Ok(())
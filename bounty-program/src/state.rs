@@ -1,12 +1,124 @@
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{
+    maybestd::io::{Read, Result as IoResult, Write},
+    BorshDeserialize, BorshSerialize,
+};
 use solana_program::{
+    hash::hashv,
     program_error::ProgramError,
     pubkey::Pubkey,
     sysvar::{clock::Clock, Sysvar},
 };
 
+use crate::{
+    error::BountyError,
+    instruction::{BOUNTY_SEED_PREFIX, TOKEN_VAULT_SEED_PREFIX},
+    url::MAX_HOST_LEN,
+};
+
+/// Length of the header at the start of every program-owned account
+///
+/// The header is an 8-byte discriminator identifying the account type,
+/// followed by a one-byte layout version.
+pub const ACCOUNT_HEADER_LEN: usize = 8 + 1;
+
+/// State stored in a program-owned account, prefixed with a type header
+///
+/// `unpack` rejects accounts whose discriminator or version don't match, so
+/// one account type can never be decoded as another.
+pub trait AccountState: BorshSerialize + BorshDeserialize {
+    /// Type tag, the first 8 bytes of `sha256("account:<TypeName>")`
+    const DISCRIMINATOR: [u8; 8];
+
+    /// Current layout version
+    const VERSION: u8;
+
+    /// Check that the account data starts with this type's header
+    fn check_header(data: &[u8]) -> Result<(), ProgramError> {
+        if data.len() < ACCOUNT_HEADER_LEN || data[..8] != Self::DISCRIMINATOR {
+            return Err(BountyError::InvalidAccountDiscriminator.into());
+        }
+
+        if data[8] != Self::VERSION {
+            return Err(BountyError::UnsupportedAccountVersion.into());
+        }
+
+        Ok(())
+    }
+
+    /// Deserialize the account state, checking the header first
+    ///
+    /// Accounts may be larger than the serialized state, so trailing bytes are ignored.
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        Self::check_header(data)?;
+
+        let mut data = &data[ACCOUNT_HEADER_LEN..];
+        Self::deserialize(&mut data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Serialize the account state behind its header
+    fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        if dst.len() < ACCOUNT_HEADER_LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+
+        dst[..8].copy_from_slice(&Self::DISCRIMINATOR);
+        dst[8] = Self::VERSION;
+        self.serialize(&mut &mut dst[ACCOUNT_HEADER_LEN..])?;
+        Ok(())
+    }
+}
+
+/// Byte offsets of the fixed-size bounty fields within the account data
+///
+/// These fields sit before any variable-length data, so clients can use them
+/// in `getProgramAccounts` memcmp filters, e.g. all Available bounties of a
+/// creator or all bounties locked by a claimant. Unset optional keys are
+/// stored as all zeros.
+pub const BOUNTY_STATE_OFFSET: usize = ACCOUNT_HEADER_LEN;
+pub const BOUNTY_CREATOR_OFFSET: usize = BOUNTY_STATE_OFFSET + 1;
+pub const BOUNTY_TOKEN_MINT_OFFSET: usize = BOUNTY_CREATOR_OFFSET + 32;
+pub const BOUNTY_CLAIMANT_OFFSET: usize = BOUNTY_TOKEN_MINT_OFFSET + 32;
+pub const BOUNTY_DEADLINE_OFFSET: usize = BOUNTY_CLAIMANT_OFFSET + 32;
+pub const BOUNTY_REPOSITORY_HASH_OFFSET: usize = BOUNTY_DEADLINE_OFFSET + 8;
+pub const BOUNTY_ISSUE_HASH_OFFSET: usize = BOUNTY_REPOSITORY_HASH_OFFSET + 32;
+pub const BOUNTY_AMOUNT_OFFSET: usize = BOUNTY_ISSUE_HASH_OFFSET + 32;
+pub const BOUNTY_CREATED_AT_OFFSET: usize = BOUNTY_AMOUNT_OFFSET + 8;
+pub const BOUNTY_LOCKED_AT_OFFSET: usize = BOUNTY_CREATED_AT_OFFSET + 8;
+pub const BOUNTY_CLAIMED_AT_OFFSET: usize = BOUNTY_LOCKED_AT_OFFSET + 8;
+pub const BOUNTY_COMPLETED_AT_OFFSET: usize = BOUNTY_CLAIMED_AT_OFFSET + 8;
+pub const BOUNTY_FEE_COLLECTOR_OFFSET: usize = BOUNTY_COMPLETED_AT_OFFSET + 8;
+pub const BOUNTY_FEE_PERCENTAGE_OFFSET: usize = BOUNTY_FEE_COLLECTOR_OFFSET + 32;
+pub const BOUNTY_BUMP_OFFSET: usize = BOUNTY_FEE_PERCENTAGE_OFFSET + 1;
+pub const BOUNTY_VAULT_BUMP_OFFSET: usize = BOUNTY_BUMP_OFFSET + 1;
+pub const BOUNTY_PLATFORM_OFFSET: usize = BOUNTY_VAULT_BUMP_OFFSET + 1;
+pub const BOUNTY_SEED_CREATOR_OFFSET: usize = BOUNTY_PLATFORM_OFFSET + 1;
+pub const BOUNTY_PENDING_CREATOR_OFFSET: usize = BOUNTY_SEED_CREATOR_OFFSET + 32;
+pub const BOUNTY_UNFROZEN_STATE_OFFSET: usize = BOUNTY_PENDING_CREATOR_OFFSET + 32;
+pub const BOUNTY_MODERATION_REASON_OFFSET: usize = BOUNTY_UNFROZEN_STATE_OFFSET + 1;
+pub const BOUNTY_LEGACY_ADDRESS_OFFSET: usize = BOUNTY_MODERATION_REASON_OFFSET + 1;
+
+/// Offset of the variable-length data (description, URLs) that follows the fixed fields
+pub const BOUNTY_VARIABLE_DATA_OFFSET: usize = BOUNTY_LEGACY_ADDRESS_OFFSET + 1;
+
+/// Hash identifying a repository, stored at `BOUNTY_REPOSITORY_HASH_OFFSET`
+///
+/// The URL is lowercased and any trailing `/` or `.git` is dropped before hashing.
+pub fn hash_repository_url(repository_url: &str) -> [u8; 32] {
+    let normalized = repository_url.trim().to_ascii_lowercase();
+    let normalized = normalized.trim_end_matches('/');
+    let normalized = normalized.strip_suffix(".git").unwrap_or(normalized);
+    hashv(&[normalized.as_bytes()]).to_bytes()
+}
+
+/// Hash identifying a self-hosted forge host in its allowed host PDA seeds
+///
+/// Host names are case-insensitive, so the host is lowercased before hashing.
+pub fn hash_host(host: &str) -> [u8; 32] {
+    hashv(&[host.to_ascii_lowercase().as_bytes()]).to_bytes()
+}
+
 /// Status of a bounty in the system
-#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
 pub enum BountyStatus {
     /// Bounty is available for claiming
     Available,
@@ -22,8 +134,42 @@ pub enum BountyStatus {
     
     /// Bounty has been cancelled by creator
     Cancelled,
+
+    /// Bounty is frozen by a moderator, see `BountyHeader::unfrozen_state`
+    Frozen,
+}
+
+/// Code forge hosting a bounty's repository
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgePlatform {
+    /// github.com
+    GitHub,
+
+    /// gitlab.com or a self-hosted GitLab instance
+    GitLab,
+
+    /// A self-hosted Gitea instance
+    Gitea,
+
+    /// bitbucket.org
+    Bitbucket,
 }
 
+impl ForgePlatform {
+    /// Lowercase name used in canonical issue identifiers
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForgePlatform::GitHub => "github",
+            ForgePlatform::GitLab => "gitlab",
+            ForgePlatform::Gitea => "gitea",
+            ForgePlatform::Bitbucket => "bitbucket",
+        }
+    }
+}
+
+/// Seconds a completed bounty is kept on-chain before it can be closed
+pub const CLOSE_RETENTION_PERIOD: i64 = 7 * 24 * 60 * 60;
+
 /// Add a fee structure
 pub struct FeeConfig {
     pub fee_collector: Pubkey,
@@ -38,101 +184,246 @@ impl FeeConfig {
     }
 }
 
-/// Bounty account structure that holds all information about a bounty
+/// Fixed-size fields of a bounty, stored at the `BOUNTY_*_OFFSET` offsets
 ///
-/// This account is created as a PDA (Program Derived Address) based on the
-/// issue hash, ensuring one unique bounty per GitHub issue.
+/// `unpack` and `pack` only touch this part of the account, so instructions
+/// that just move a bounty between states never decode or rewrite its strings.
 ///
 /// State transitions:
 /// - Available -> Locked -> Claimed -> Completed
 /// - Available -> Cancelled
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct Bounty {
-    /// The wallet that created the bounty
-    pub creator: Pubkey,
-    
-    /// Amount of tokens allocated for the bounty (in lamports for SOL, or token amount for SPL tokens)
-    pub amount: u64,
-    
-    /// Description of the bounty task
-    pub description: String,
-    
-    /// Hash of the GitHub issue URL (used as a unique identifier)
-    pub issue_hash: [u8; 32],
-    
-    /// GitHub issue URL in text form
-    pub issue_url: String,
-    
-    /// GitHub repository URL
-    pub repository_url: String,
-    
-    /// Timestamp when the bounty expires
-    pub deadline: i64,
-    
+/// - Available, Locked or Claimed -> Frozen -> back to where it was
+/// - Available, Locked, Claimed or Frozen -> Cancelled, by a moderator
+#[derive(Debug, PartialEq)]
+pub struct BountyHeader {
     /// Current status of the bounty
     pub state: BountyStatus,
-    
+
+    /// The wallet that currently manages the bounty
+    ///
+    /// This is the creator until ownership is transferred, see `seed_creator`.
+    pub creator: Pubkey,
+
     /// Token mint address (for SPL token bounties) - None for SOL bounties
     pub token_mint: Option<Pubkey>,
-    
+
     /// Wallet address of the developer who claimed the bounty
     pub claimant: Option<Pubkey>,
-    
-    /// GitHub PR URL submitted as the solution
-    pub pr_url: Option<String>,
-    
+
+    /// Timestamp when the bounty expires
+    pub deadline: i64,
+
+    /// Hash of the normalized repository URL, see `hash_repository_url`
+    pub repository_hash: [u8; 32],
+
+    /// Hash of the canonical issue identifier, see `IssueId`
+    pub issue_hash: [u8; 32],
+
+    /// Amount of tokens allocated for the bounty (in lamports for SOL, or token amount for SPL tokens)
+    pub amount: u64,
+
+    /// Timestamp when the bounty was created
+    pub created_at: i64,
+
     /// Timestamp when the bounty was locked
     pub locked_at: Option<i64>,
-    
+
     /// Timestamp when the bounty was claimed
     pub claimed_at: Option<i64>,
-    
+
     /// Timestamp when the bounty was completed (funds transferred)
     pub completed_at: Option<i64>,
-    
-    /// Timestamp when the bounty was created
-    pub created_at: i64,
-    
+
     /// New field for fee destination
     pub fee_collector: Option<Pubkey>,
-    
+
     /// Base points (1% = 100)
     pub fee_percentage: u8,
+
+    /// Bump seed of the bounty PDA, which also signs for token vaults
+    pub bump: u8,
+
+    /// Bump seed of the bounty's SOL or token vault PDA
+    pub vault_bump: u8,
+
+    /// Forge hosting the bounty's repository, issue and pull request
+    pub platform: ForgePlatform,
+
+    /// The wallet that created the bounty, part of the bounty PDA seeds
+    ///
+    /// Unlike `creator` it never changes, so the bounty keeps its address
+    /// and signing seeds when ownership is transferred.
+    pub seed_creator: Pubkey,
+
+    /// Wallet a transfer of ownership was offered to, until it accepts
+    pub pending_creator: Option<Pubkey>,
+
+    /// Status the bounty returns to when unfrozen, only meaningful while frozen
+    pub unfrozen_state: BountyStatus,
+
+    /// Reason code a moderator cancelled the bounty with, zero otherwise
+    pub moderation_reason: u8,
+
+    /// Whether the bounty lives at a client-chosen address instead of its PDA
+    ///
+    /// Only bounties created before bounty PDAs were enforced and migrated
+    /// since. They can't sign, so their token vault owns itself and `bump`
+    /// is unused.
+    pub legacy_address: bool,
 }
 
-impl Bounty {
-    /// Size of the bounty account for space allocation
-    pub const LEN: usize = 32 + 8 + 256 + 32 + 256 + 256 + 8 + 1 + 33 + 33 + 256 + 9 + 9 + 9 + 8 + 33 + 1;
-    
-    /// Calculate the exact account size needed for a specific bounty
+/// Write an optional key as 32 bytes, all zeros when unset
+fn write_optional_pubkey<W: Write>(key: &Option<Pubkey>, writer: &mut W) -> IoResult<()> {
+    key.unwrap_or_default().serialize(writer)
+}
+
+fn read_optional_pubkey<R: Read>(reader: &mut R) -> IoResult<Option<Pubkey>> {
+    let key = Pubkey::deserialize_reader(reader)?;
+    Ok(if key == Pubkey::default() { None } else { Some(key) })
+}
+
+/// Write an optional timestamp as 8 bytes, zero when unset
+fn write_optional_timestamp<W: Write>(timestamp: &Option<i64>, writer: &mut W) -> IoResult<()> {
+    timestamp.unwrap_or_default().serialize(writer)
+}
+
+fn read_optional_timestamp<R: Read>(reader: &mut R) -> IoResult<Option<i64>> {
+    let timestamp = i64::deserialize_reader(reader)?;
+    Ok(if timestamp == 0 { None } else { Some(timestamp) })
+}
+
+impl BorshSerialize for BountyHeader {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.state.serialize(writer)?;
+        self.creator.serialize(writer)?;
+        write_optional_pubkey(&self.token_mint, writer)?;
+        write_optional_pubkey(&self.claimant, writer)?;
+        self.deadline.serialize(writer)?;
+        self.repository_hash.serialize(writer)?;
+        self.issue_hash.serialize(writer)?;
+        self.amount.serialize(writer)?;
+        self.created_at.serialize(writer)?;
+        write_optional_timestamp(&self.locked_at, writer)?;
+        write_optional_timestamp(&self.claimed_at, writer)?;
+        write_optional_timestamp(&self.completed_at, writer)?;
+        write_optional_pubkey(&self.fee_collector, writer)?;
+        self.fee_percentage.serialize(writer)?;
+        self.bump.serialize(writer)?;
+        self.vault_bump.serialize(writer)?;
+        self.platform.serialize(writer)?;
+        self.seed_creator.serialize(writer)?;
+        write_optional_pubkey(&self.pending_creator, writer)?;
+        self.unfrozen_state.serialize(writer)?;
+        self.moderation_reason.serialize(writer)?;
+        self.legacy_address.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for BountyHeader {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        Self::deserialize_version(reader, Bounty::VERSION)
+    }
+}
+
+impl BountyHeader {
+    /// Deserialize the fixed-size fields as laid out by a given bounty version
     ///
-    /// This calculates more accurately than the constant LEN,
-    /// taking into account the actual string lengths.
-    pub fn get_account_size(
-        description: &str,
-        issue_url: &str,
-        repository_url: &str,
-        pr_url_max_len: usize,
-    ) -> usize {
-        // Size of fixed fields
-        let fixed_size = std::mem::size_of::<Pubkey>() + // creator
-            std::mem::size_of::<u64>() + // amount
-            std::mem::size_of::<BountyStatus>() + // state
-            std::mem::size_of::<Option<Pubkey>>() + // token_mint
-            std::mem::size_of::<Option<Pubkey>>() + // claimant
-            std::mem::size_of::<i64>() + // deadline
-            std::mem::size_of::<Option<i64>>() * 3 + // locked_at, claimed_at, completed_at
-            std::mem::size_of::<i64>() + // created_at
-            32; // issue_hash [u8; 32]
-
-        // Size of variable length fields
-        let string_size = description.len() +
-            issue_url.len() +
-            repository_url.len() +
-            pr_url_max_len; // Maximum size for PR URL
-
-        // Add some buffer for serialization overhead
-        fixed_size + string_size + 100 // Buffer for Borsh serialization
+    /// Version 2 accounts have no bump seeds, these are left at zero for the
+    /// migration to fill in. Bounties before version 4 are all on GitHub,
+    /// before version 5 ownership couldn't be transferred, before version 6
+    /// bounties couldn't be moderated, and before version 7 every bounty that
+    /// was migrated lived at its PDA.
+    pub fn deserialize_version<R: Read>(reader: &mut R, version: u8) -> IoResult<Self> {
+        let mut header = Self {
+            state: BountyStatus::deserialize_reader(reader)?,
+            creator: Pubkey::deserialize_reader(reader)?,
+            token_mint: read_optional_pubkey(reader)?,
+            claimant: read_optional_pubkey(reader)?,
+            deadline: i64::deserialize_reader(reader)?,
+            repository_hash: <[u8; 32]>::deserialize_reader(reader)?,
+            issue_hash: <[u8; 32]>::deserialize_reader(reader)?,
+            amount: u64::deserialize_reader(reader)?,
+            created_at: i64::deserialize_reader(reader)?,
+            locked_at: read_optional_timestamp(reader)?,
+            claimed_at: read_optional_timestamp(reader)?,
+            completed_at: read_optional_timestamp(reader)?,
+            fee_collector: read_optional_pubkey(reader)?,
+            fee_percentage: u8::deserialize_reader(reader)?,
+            bump: 0,
+            vault_bump: 0,
+            platform: ForgePlatform::GitHub,
+            seed_creator: Pubkey::default(),
+            pending_creator: None,
+            unfrozen_state: BountyStatus::Available,
+            moderation_reason: 0,
+            legacy_address: false,
+        };
+        header.seed_creator = header.creator;
+
+        if version >= 3 {
+            header.bump = u8::deserialize_reader(reader)?;
+            header.vault_bump = u8::deserialize_reader(reader)?;
+        }
+
+        if version >= 4 {
+            header.platform = ForgePlatform::deserialize_reader(reader)?;
+        }
+
+        if version >= 5 {
+            header.seed_creator = Pubkey::deserialize_reader(reader)?;
+            header.pending_creator = read_optional_pubkey(reader)?;
+        }
+
+        if version >= 6 {
+            header.unfrozen_state = BountyStatus::deserialize_reader(reader)?;
+            header.moderation_reason = u8::deserialize_reader(reader)?;
+        }
+
+        if version >= 7 {
+            header.legacy_address = bool::deserialize_reader(reader)?;
+        }
+
+        Ok(header)
+    }
+
+    /// Seeds the bounty PDA signs with, using the stored bump
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            BOUNTY_SEED_PREFIX,
+            &self.issue_hash,
+            self.seed_creator.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    /// Seeds the owner of the bounty's token vault signs with
+    ///
+    /// That's the bounty PDA, or the vault itself for bounties at a legacy address.
+    pub fn vault_authority_seeds<'a>(&'a self, bounty: &'a Pubkey) -> Vec<&'a [u8]> {
+        if self.legacy_address {
+            vec![TOKEN_VAULT_SEED_PREFIX, bounty.as_ref(), std::slice::from_ref(&self.vault_bump)]
+        } else {
+            self.signer_seeds().to_vec()
+        }
+    }
+
+    /// Read the fixed-size fields of a bounty account, checking the account header
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        Bounty::check_header(data)?;
+
+        let mut fixed = data
+            .get(ACCOUNT_HEADER_LEN..BOUNTY_VARIABLE_DATA_OFFSET)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Self::deserialize(&mut fixed).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Write the fixed-size fields back, leaving the rest of the account untouched
+    pub fn pack(&self, dst: &mut [u8]) -> Result<(), ProgramError> {
+        let mut fixed = dst
+            .get_mut(ACCOUNT_HEADER_LEN..BOUNTY_VARIABLE_DATA_OFFSET)
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+        self.serialize(&mut fixed)?;
+        Ok(())
     }
 
     /// Calculate fee amount for this bounty
@@ -147,84 +438,12 @@ impl Bounty {
         self.amount.saturating_sub(fee)
     }
 
-    /// Create a new SOL bounty
-    ///
-    /// Initializes a new bounty using SOL as the reward token
-    pub fn new_sol_bounty(
-        creator: Pubkey,
-        amount: u64,
-        description: String,
-        issue_hash: [u8; 32],
-        issue_url: &str,
-        repository_url: &str,
-        deadline: i64,
-        fee_collector: Option<Pubkey>,
-        fee_percentage: u8,
-    ) -> Self {
-        let clock = Clock::get().unwrap();
-        Self {
-            creator,
-            amount,
-            description,
-            issue_hash,
-            issue_url: issue_url.to_string(),
-            repository_url: repository_url.to_string(),
-            deadline,
-            state: BountyStatus::Available,
-            token_mint: None,
-            claimant: None,
-            pr_url: None,
-            locked_at: None,
-            claimed_at: None,
-            completed_at: None,
-            created_at: clock.unix_timestamp,
-            fee_collector,
-            fee_percentage,
-        }
-    }
-
-    /// Create a new SPL token bounty
-    ///
-    /// Initializes a new bounty using an SPL token (like USDC) as the reward
-    pub fn new_token_bounty(
-        creator: Pubkey,
-        amount: u64,
-        description: String,
-        issue_hash: [u8; 32],
-        issue_url: &str,
-        repository_url: &str,
-        deadline: i64,
-        token_mint: Pubkey,
-        fee_collector: Option<Pubkey>,
-        fee_percentage: u8,
-    ) -> Self {
-        let clock = Clock::get().unwrap();
-        Self {
-            creator,
-            amount,
-            description,
-            issue_hash,
-            issue_url: issue_url.to_string(),
-            repository_url: repository_url.to_string(),
-            deadline,
-            state: BountyStatus::Available,
-            token_mint: Some(token_mint),
-            claimant: None,
-            pr_url: None,
-            locked_at: None,
-            claimed_at: None,
-            completed_at: None,
-            created_at: clock.unix_timestamp,
-            fee_collector,
-            fee_percentage,
-        }
-    }
-
     /// Lock a bounty to a specific contributor
     ///
     /// This reserves the bounty for a contributor who intends to work on it.
-    /// Ensures bounty is in the Available state before locking.
-    pub fn lock(&mut self, claimant: Pubkey, pr_url: String) -> Result<(), ProgramError> {
+    /// Ensures bounty is in the Available state before locking. The PR URL
+    /// is stored separately, see `Bounty::pack_pr_url`.
+    pub fn lock(&mut self, claimant: Pubkey) -> Result<(), ProgramError> {
         if self.state != BountyStatus::Available {
             return Err(ProgramError::InvalidAccountData);
         }
@@ -236,7 +455,6 @@ impl Bounty {
 
         self.state = BountyStatus::Locked;
         self.claimant = Some(claimant);
-        self.pr_url = Some(pr_url);
         self.locked_at = Some(clock.unix_timestamp);
         Ok(())
     }
@@ -275,6 +493,23 @@ impl Bounty {
         Ok(())
     }
 
+    /// Settle a dispute over a claimed bounty
+    ///
+    /// Awarding it to the claimant completes it, otherwise it's cancelled so
+    /// the creator can be refunded.
+    pub fn resolve_dispute(&mut self, award_claimant: bool) -> Result<(), ProgramError> {
+        if self.state != BountyStatus::Claimed {
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        if award_claimant {
+            return self.complete();
+        }
+
+        self.state = BountyStatus::Cancelled;
+        Ok(())
+    }
+
     /// Cancel an available bounty
     ///
     /// This allows the creator to cancel the bounty and reclaim funds,
@@ -287,4 +522,889 @@ impl Bounty {
         self.state = BountyStatus::Cancelled;
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Freeze an open bounty, remembering its state for `unfreeze`
+    ///
+    /// Frozen bounties can't be locked, claimed or completed, as all of these
+    /// require another state.
+    pub fn freeze(&mut self) -> Result<(), ProgramError> {
+        if self.is_terminal() || self.state == BountyStatus::Frozen {
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        self.unfrozen_state = self.state;
+        self.state = BountyStatus::Frozen;
+        Ok(())
+    }
+
+    /// Return a frozen bounty to the state it was frozen in
+    pub fn unfreeze(&mut self) -> Result<(), ProgramError> {
+        if self.state != BountyStatus::Frozen {
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        self.state = self.unfrozen_state;
+        self.unfrozen_state = BountyStatus::Available;
+        Ok(())
+    }
+
+    /// Cancel an open or frozen bounty on a moderator's behalf
+    ///
+    /// `reason` is recorded on the bounty and must be non-zero.
+    pub fn force_cancel(&mut self, reason: u8) -> Result<(), ProgramError> {
+        if self.is_terminal() {
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        if reason == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.state = BountyStatus::Cancelled;
+        self.unfrozen_state = BountyStatus::Available;
+        self.moderation_reason = reason;
+        Ok(())
+    }
+
+    /// Offer ownership of the bounty to `new_creator`
+    ///
+    /// Offering it to the current creator withdraws a pending offer.
+    pub fn offer_ownership(&mut self, new_creator: Pubkey) {
+        self.pending_creator = if new_creator == self.creator { None } else { Some(new_creator) };
+    }
+
+    /// Accept a pending ownership offer, making `new_creator` the creator
+    pub fn accept_ownership(&mut self, new_creator: &Pubkey) -> Result<(), ProgramError> {
+        if self.pending_creator.as_ref() != Some(new_creator) {
+            return Err(BountyError::Unauthorized.into());
+        }
+
+        self.creator = *new_creator;
+        self.pending_creator = None;
+        Ok(())
+    }
+
+    /// Check whether the bounty has reached a terminal state
+    pub fn is_terminal(&self) -> bool {
+        self.state == BountyStatus::Completed || self.state == BountyStatus::Cancelled
+    }
+
+    /// Check that the bounty account can be closed
+    ///
+    /// Only completed or cancelled bounties can be closed. Completed bounties
+    /// are kept for `CLOSE_RETENTION_PERIOD` seconds so the final record stays
+    /// readable for a while after the payout.
+    pub fn check_closable(&self, now: i64) -> Result<(), ProgramError> {
+        if !self.is_terminal() {
+            return Err(BountyError::InvalidBountyState.into());
+        }
+
+        if let Some(completed_at) = self.completed_at {
+            let closable_at = completed_at
+                .checked_add(CLOSE_RETENTION_PERIOD)
+                .ok_or(BountyError::Overflow)?;
+            if now < closable_at {
+                return Err(BountyError::RetentionPeriodActive.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Bounty account structure that holds all information about a bounty
+///
+/// This account is created as a PDA (Program Derived Address) based on the
+/// issue hash and the creator, ensuring one unique bounty per issue for each
+/// creator. The issue hash is checked against `issue_url` on creation, see
+/// `IssueId`.
+///
+/// The fixed-size fields in `header` are serialized first, followed by the
+/// strings. The PR URL comes last so it can be set without moving the rest.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Bounty {
+    /// Fixed-size fields, including the bounty state
+    pub header: BountyHeader,
+
+    /// Description of the bounty task
+    pub description: String,
+
+    /// GitHub issue URL in text form
+    pub issue_url: String,
+
+    /// GitHub repository URL
+    pub repository_url: String,
+
+    /// GitHub PR URL submitted as the solution
+    pub pr_url: Option<String>,
+}
+
+impl AccountState for Bounty {
+    const DISCRIMINATOR: [u8; 8] = [237, 16, 105, 198, 19, 69, 242, 234];
+    const VERSION: u8 = 7;
+}
+
+impl Bounty {
+    /// Size of the bounty account for space allocation
+    ///
+    /// Kept for accounts created before accounts were sized from their contents.
+    pub const LEN: usize = 32 + 8 + 256 + 32 + 256 + 256 + 8 + 1 + 33 + 33 + 256 + 9 + 9 + 9 + 8 + 33 + 1;
+
+    /// Calculate the exact account size needed for a specific bounty
+    ///
+    /// Includes the account header and the fixed-size fields, which keep
+    /// their size whether set or not, so locking, claiming and completing
+    /// never grow the account. Only the strings are sized from their actual
+    /// length.
+    pub fn get_account_size(
+        description: &str,
+        issue_url: &str,
+        repository_url: &str,
+        pr_url_len: usize,
+    ) -> usize {
+        // Borsh prefixes every string with a u32 length
+        let string_size = 4 + description.len() +
+            4 + issue_url.len() +
+            4 + repository_url.len() +
+            1 + 4 + pr_url_len; // Option tag, length and PR URL
+
+        BOUNTY_VARIABLE_DATA_OFFSET + string_size
+    }
+
+    /// Account size needed to store this bounty as it currently is
+    pub fn account_size(&self) -> usize {
+        Self::get_account_size(
+            &self.description,
+            &self.issue_url,
+            &self.repository_url,
+            self.pr_url.as_ref().map_or(0, |pr_url| pr_url.len()),
+        )
+    }
+
+    /// Whether two bounties were created with the same parameters
+    ///
+    /// Only the parameters given at creation are compared, not the lifecycle
+    /// state, timestamps or bumps.
+    pub fn has_same_parameters(&self, other: &Bounty) -> bool {
+        self.header.seed_creator == other.header.seed_creator
+            && self.header.issue_hash == other.header.issue_hash
+            && self.header.token_mint == other.header.token_mint
+            && self.header.amount == other.header.amount
+            && self.header.deadline == other.header.deadline
+            && self.header.fee_collector == other.header.fee_collector
+            && self.header.fee_percentage == other.header.fee_percentage
+            && self.header.platform == other.header.platform
+            && self.description == other.description
+            && self.issue_url == other.issue_url
+            && self.repository_url == other.repository_url
+    }
+
+    /// Find the offset of the PR URL in bounty account data
+    ///
+    /// Skips over the length-prefixed strings stored before it without decoding them.
+    pub fn pr_url_offset(data: &[u8]) -> Result<usize, ProgramError> {
+        // description, issue_url, repository_url
+        Self::skip_strings(data, 3)
+    }
+
+    /// Read the repository URL from bounty account data without decoding the rest
+    pub fn unpack_repository_url(data: &[u8]) -> Result<&str, ProgramError> {
+        // description, issue_url
+        let offset = Self::skip_strings(data, 2)?;
+        let len_bytes = data
+            .get(offset..offset + 4)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+        let url_bytes = data
+            .get(offset + 4..offset + 4 + len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        core::str::from_utf8(url_bytes).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Offset just past the first `count` length-prefixed strings after the fixed fields
+    fn skip_strings(data: &[u8], count: usize) -> Result<usize, ProgramError> {
+        let mut offset = BOUNTY_VARIABLE_DATA_OFFSET;
+
+        for _ in 0..count {
+            let len_bytes = data
+                .get(offset..offset + 4)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            let len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+            offset = offset
+                .checked_add(4 + len)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+
+        Ok(offset)
+    }
+
+    /// Read the PR URL stored at `offset`, see `pr_url_offset`
+    pub fn unpack_pr_url(data: &[u8], offset: usize) -> Result<Option<String>, ProgramError> {
+        let mut pr_url_data = data.get(offset..).ok_or(ProgramError::InvalidAccountData)?;
+        Option::<String>::deserialize(&mut pr_url_data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Write the PR URL at `offset`, see `pr_url_offset`
+    pub fn pack_pr_url(dst: &mut [u8], offset: usize, pr_url: &Option<String>) -> Result<(), ProgramError> {
+        let mut pr_url_data = dst.get_mut(offset..).ok_or(ProgramError::AccountDataTooSmall)?;
+        pr_url.serialize(&mut pr_url_data)?;
+        Ok(())
+    }
+
+    /// Create a new SOL bounty
+    ///
+    /// Initializes a new bounty using SOL as the reward token
+    pub fn new_sol_bounty(
+        creator: Pubkey,
+        amount: u64,
+        description: String,
+        issue_hash: [u8; 32],
+        issue_url: &str,
+        repository_url: &str,
+        deadline: i64,
+        fee_collector: Option<Pubkey>,
+        fee_percentage: u8,
+        bump: u8,
+        vault_bump: u8,
+        platform: ForgePlatform,
+    ) -> Self {
+        let clock = Clock::get().unwrap();
+        Self {
+            header: BountyHeader {
+                state: BountyStatus::Available,
+                creator,
+                token_mint: None,
+                claimant: None,
+                deadline,
+                repository_hash: hash_repository_url(repository_url),
+                issue_hash,
+                amount,
+                created_at: clock.unix_timestamp,
+                locked_at: None,
+                claimed_at: None,
+                completed_at: None,
+                fee_collector,
+                fee_percentage,
+                bump,
+                vault_bump,
+                platform,
+                seed_creator: creator,
+                pending_creator: None,
+                unfrozen_state: BountyStatus::Available,
+                moderation_reason: 0,
+                legacy_address: false,
+            },
+            description,
+            issue_url: issue_url.to_string(),
+            repository_url: repository_url.to_string(),
+            pr_url: None,
+        }
+    }
+
+    /// Create a new SPL token bounty
+    ///
+    /// Initializes a new bounty using an SPL token (like USDC) as the reward
+    pub fn new_token_bounty(
+        creator: Pubkey,
+        amount: u64,
+        description: String,
+        issue_hash: [u8; 32],
+        issue_url: &str,
+        repository_url: &str,
+        deadline: i64,
+        token_mint: Pubkey,
+        fee_collector: Option<Pubkey>,
+        fee_percentage: u8,
+        bump: u8,
+        vault_bump: u8,
+        platform: ForgePlatform,
+    ) -> Self {
+        let clock = Clock::get().unwrap();
+        Self {
+            header: BountyHeader {
+                state: BountyStatus::Available,
+                creator,
+                token_mint: Some(token_mint),
+                claimant: None,
+                deadline,
+                repository_hash: hash_repository_url(repository_url),
+                issue_hash,
+                amount,
+                created_at: clock.unix_timestamp,
+                locked_at: None,
+                claimed_at: None,
+                completed_at: None,
+                fee_collector,
+                fee_percentage,
+                bump,
+                vault_bump,
+                platform,
+                seed_creator: creator,
+                pending_creator: None,
+                unfrozen_state: BountyStatus::Available,
+                moderation_reason: 0,
+                legacy_address: false,
+            },
+            description,
+            issue_url: issue_url.to_string(),
+            repository_url: repository_url.to_string(),
+            pr_url: None,
+        }
+    }
+}
+
+/// Action a delegate may take on behalf of a creator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DelegatePermission {
+    /// Create bounties owned by the creator, funded by the delegate
+    Create,
+    /// Complete claimed bounties, paying the claimant
+    Complete,
+    /// Cancel or reduce available bounties, refunding the creator
+    Cancel,
+    /// Top up bounties and edit their description and URLs
+    Extend,
+}
+
+/// Permissions granted to a delegate, a delegate without any is removed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DelegatePermissions {
+    pub can_create: bool,
+    pub can_complete: bool,
+    pub can_cancel: bool,
+    pub can_extend: bool,
+}
+
+impl DelegatePermissions {
+    pub fn allows(&self, permission: DelegatePermission) -> bool {
+        match permission {
+            DelegatePermission::Create => self.can_create,
+            DelegatePermission::Complete => self.can_complete,
+            DelegatePermission::Cancel => self.can_cancel,
+            DelegatePermission::Extend => self.can_extend,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delegate {
+    pub key: Pubkey,
+    pub permissions: DelegatePermissions,
+}
+
+/// Keys allowed to manage a creator's bounties
+///
+/// This account is a PDA derived from the creator, see
+/// `find_org_authority_address`, so each creator has a single list of
+/// delegates covering all of their bounties.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct OrgAuthority {
+    /// The creator whose bounties the delegates manage
+    pub creator: Pubkey,
+
+    /// Bump of the PDA
+    pub bump: u8,
+
+    /// Delegates and what each of them may do
+    pub delegates: Vec<Delegate>,
+}
+
+impl AccountState for OrgAuthority {
+    const DISCRIMINATOR: [u8; 8] = [160, 255, 13, 131, 107, 93, 205, 64];
+    const VERSION: u8 = 1;
+}
+
+impl OrgAuthority {
+    /// Maximum number of delegates per creator
+    pub const MAX_DELEGATES: usize = 16;
+
+    /// Account size with room for `MAX_DELEGATES` delegates
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 32 + 1 + 4 + Self::MAX_DELEGATES * (32 + 4);
+
+    pub fn new(creator: Pubkey, bump: u8) -> Self {
+        Self {
+            creator,
+            bump,
+            delegates: Vec::new(),
+        }
+    }
+
+    /// Check whether `key` is a delegate with `permission`
+    pub fn allows(&self, key: &Pubkey, permission: DelegatePermission) -> bool {
+        self.delegates
+            .iter()
+            .any(|delegate| delegate.key == *key && delegate.permissions.allows(permission))
+    }
+
+    /// Add a delegate or replace its permissions, removing it when they are empty
+    pub fn set_delegate(&mut self, key: Pubkey, permissions: DelegatePermissions) -> Result<(), BountyError> {
+        let existing = self.delegates.iter().position(|delegate| delegate.key == key);
+
+        match (existing, permissions.is_empty()) {
+            (Some(index), true) => {
+                self.delegates.remove(index);
+            }
+            (Some(index), false) => self.delegates[index].permissions = permissions,
+            (None, true) => {}
+            (None, false) => {
+                if self.delegates.len() >= Self::MAX_DELEGATES {
+                    return Err(BountyError::TooManyDelegates);
+                }
+                self.delegates.push(Delegate { key, permissions });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Program-wide switches the admin uses to respond to incidents
+///
+/// This account is a PDA, see `find_program_config_address`. Until it is
+/// created the program runs unpaused with every instruction enabled.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProgramConfig {
+    /// Root admin, holds `Role::Admin` without a role assignment
+    pub admin: Pubkey,
+
+    /// Bump of the PDA
+    pub bump: u8,
+
+    /// Blocks every instruction except creators' withdrawals
+    pub paused: bool,
+
+    /// Bit `n` disables the instruction with index `n` in `BountyInstruction`
+    pub disabled_instructions: u64,
+
+    /// Fee collector recorded on new bounties
+    pub fee_collector: Option<Pubkey>,
+
+    /// Fee in basis points recorded on new bounties
+    pub fee_percentage: u8,
+
+    /// Id of the next admin proposal, see `AdminProposal`
+    pub next_proposal_id: u64,
+}
+
+impl AccountState for ProgramConfig {
+    const DISCRIMINATOR: [u8; 8] = [196, 210, 90, 231, 144, 149, 140, 63];
+    const VERSION: u8 = 1;
+}
+
+impl ProgramConfig {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 32 + 1 + 1 + 8 + 33 + 1 + 8;
+
+    /// Seconds between proposing an admin action and being able to execute it
+    pub const ACTION_DELAY: i64 = 2 * 24 * 60 * 60;
+
+    pub fn new(admin: Pubkey, bump: u8) -> Self {
+        Self {
+            admin,
+            bump,
+            paused: false,
+            disabled_instructions: 0,
+            fee_collector: None,
+            fee_percentage: 0,
+            next_proposal_id: 0,
+        }
+    }
+
+    /// Fees recorded on new bounties, none until the config is created
+    pub fn fees(config: Option<&Self>) -> (Option<Pubkey>, u8) {
+        config.map_or((None, 0), |config| (config.fee_collector, config.fee_percentage))
+    }
+
+    /// Check that the instruction with `index` may run
+    ///
+    /// Withdrawals and moderation stay available while paused, as signalled by
+    /// `runs_while_paused`, but can be disabled individually.
+    pub fn check_instruction(&self, index: u8, runs_while_paused: bool) -> Result<(), BountyError> {
+        if self.paused && !runs_while_paused {
+            return Err(BountyError::ProgramPaused);
+        }
+
+        if index < 64 && self.disabled_instructions & (1 << index) != 0 {
+            return Err(BountyError::InstructionDisabled);
+        }
+
+        Ok(())
+    }
+}
+
+/// Operational roles, each held through a `RoleAssignment`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Pauses the program, manages roles and cancels proposals
+    Admin,
+    /// Proposes new webhook authorities
+    WebhookManager,
+    /// Settles disputes over claimed bounties, see `ResolveDispute`
+    Arbiter,
+    /// Acts on spam and abusive bounties
+    Moderator,
+    /// Proposes the fees charged on new bounties
+    FeeManager,
+}
+
+/// A role held by a key
+///
+/// This account is a PDA derived from the role and the holder, see
+/// `find_role_address`. It's closed when the role is revoked, so a key holds
+/// a role exactly when its assignment exists.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct RoleAssignment {
+    pub role: Role,
+
+    pub holder: Pubkey,
+
+    /// Bump of the PDA
+    pub bump: u8,
+
+    /// Admin who granted the role
+    pub granted_by: Pubkey,
+
+    /// Unix timestamp of the grant
+    pub granted_at: i64,
+}
+
+impl AccountState for RoleAssignment {
+    const DISCRIMINATOR: [u8; 8] = [205, 130, 191, 231, 211, 225, 155, 246];
+    const VERSION: u8 = 1;
+}
+
+impl RoleAssignment {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 1 + 32 + 1 + 32 + 8;
+}
+
+/// Trust changes that only take effect after `ProgramConfig::ACTION_DELAY`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub enum AdminAction {
+    /// Change the fees recorded on new bounties
+    SetFees {
+        fee_collector: Option<Pubkey>,
+        fee_percentage: u8,
+    },
+    /// Hand the program config over to another root admin
+    SetAdmin { new_admin: Pubkey },
+    /// Allow a key to complete bounties through webhooks
+    AddWebhookAuthority { authority: Pubkey, name: String },
+    /// Assign a role, the only way to grant `Role::Admin`
+    GrantRole { role: Role, holder: Pubkey },
+    /// Allow token bounties in a mint, or change its settings, see `AllowedMint`
+    AllowMint {
+        mint: Pubkey,
+        min_amount: u64,
+        fee_percentage: Option<u8>,
+    },
+    /// Allow bounties on a self-hosted GitLab or Gitea instance, see `AllowedHost`
+    AllowHost { platform: ForgePlatform, host: String },
+}
+
+impl AdminAction {
+    /// Role needed to propose and execute the action
+    pub fn required_role(&self) -> Role {
+        match self {
+            Self::SetFees { .. } => Role::FeeManager,
+            Self::AddWebhookAuthority { .. } => Role::WebhookManager,
+            Self::SetAdmin { .. }
+            | Self::GrantRole { .. }
+            | Self::AllowMint { .. }
+            | Self::AllowHost { .. } => Role::Admin,
+        }
+    }
+}
+
+/// An admin action waiting for its delay to pass
+///
+/// This account is a PDA derived from the proposal id, see
+/// `find_proposal_address`. It's closed when the action is executed or
+/// cancelled, so only pending proposals exist on chain.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AdminProposal {
+    /// Id taken from `ProgramConfig::next_proposal_id`
+    pub id: u64,
+
+    /// Bump of the PDA
+    pub bump: u8,
+
+    /// Admin who proposed the action
+    pub proposer: Pubkey,
+
+    pub action: AdminAction,
+
+    /// Unix timestamp of the proposal
+    pub proposed_at: i64,
+
+    /// Unix timestamp from which the action can be executed
+    pub executable_at: i64,
+}
+
+impl AccountState for AdminProposal {
+    const DISCRIMINATOR: [u8; 8] = [107, 249, 66, 11, 147, 28, 12, 239];
+    const VERSION: u8 = 1;
+}
+
+impl AdminProposal {
+    pub fn new(id: u64, bump: u8, proposer: Pubkey, action: AdminAction, proposed_at: i64) -> Self {
+        Self {
+            id,
+            bump,
+            proposer,
+            action,
+            proposed_at,
+            executable_at: proposed_at + ProgramConfig::ACTION_DELAY,
+        }
+    }
+
+    /// Account size needed to store this proposal
+    pub fn account_len(&self) -> usize {
+        // Serializing into a Vec can't fail
+        ACCOUNT_HEADER_LEN + borsh::to_vec(self).unwrap().len()
+    }
+}
+
+/// A mint token bounties can be created in
+///
+/// This account is a PDA derived from the mint, see
+/// `find_allowed_mint_address`. Token bounties in mints without one are
+/// rejected with `InvalidTokenMint`.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AllowedMint {
+    pub mint: Pubkey,
+
+    /// Bump of the PDA
+    pub bump: u8,
+
+    /// Smallest amount a bounty in this mint can be created with
+    pub min_amount: u64,
+
+    /// Fee in basis points replacing the config's fee for bounties in this mint
+    pub fee_percentage: Option<u8>,
+}
+
+impl AccountState for AllowedMint {
+    const DISCRIMINATOR: [u8; 8] = [173, 229, 179, 46, 121, 164, 247, 6];
+    const VERSION: u8 = 1;
+}
+
+impl AllowedMint {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 32 + 1 + 8 + 2;
+
+    /// Fees recorded on a new bounty in this mint
+    pub fn fees(&self, config: Option<&ProgramConfig>) -> (Option<Pubkey>, u8) {
+        let (fee_collector, fee_percentage) = ProgramConfig::fees(config);
+        (fee_collector, self.fee_percentage.unwrap_or(fee_percentage))
+    }
+}
+
+/// A self-hosted forge instance bounties can point to
+///
+/// This account is a PDA derived from the platform and host, see
+/// `find_allowed_host_address`. Bounties whose repository is on any host
+/// other than the public ones in `url::PUBLIC_HOSTS` are rejected with
+/// `UrlHostNotAllowed` without one.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AllowedHost {
+    /// GitLab or Gitea
+    pub platform: ForgePlatform,
+
+    /// Bump of the PDA
+    pub bump: u8,
+
+    /// Lowercased host name
+    pub host: String,
+}
+
+impl AccountState for AllowedHost {
+    const DISCRIMINATOR: [u8; 8] = [2, 45, 73, 255, 118, 175, 23, 243];
+    const VERSION: u8 = 1;
+}
+
+impl AllowedHost {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 1 + 1 + 4 + MAX_HOST_LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A header with a distinct, non-zero value in every field
+    fn header() -> BountyHeader {
+        BountyHeader {
+            state: BountyStatus::Claimed,
+            creator: Pubkey::new_from_array([1; 32]),
+            token_mint: Some(Pubkey::new_from_array([2; 32])),
+            claimant: Some(Pubkey::new_from_array([3; 32])),
+            deadline: 1_700_000_004,
+            repository_hash: [5; 32],
+            issue_hash: [6; 32],
+            amount: 7_000_000,
+            created_at: 1_700_000_008,
+            locked_at: Some(1_700_000_009),
+            claimed_at: Some(1_700_000_010),
+            completed_at: Some(1_700_000_011),
+            fee_collector: Some(Pubkey::new_from_array([12; 32])),
+            fee_percentage: 13,
+            bump: 14,
+            vault_bump: 15,
+            platform: ForgePlatform::Gitea,
+            seed_creator: Pubkey::new_from_array([17; 32]),
+            pending_creator: Some(Pubkey::new_from_array([18; 32])),
+            unfrozen_state: BountyStatus::Locked,
+            moderation_reason: 20,
+            legacy_address: true,
+        }
+    }
+
+    fn packed(header: &BountyHeader) -> Vec<u8> {
+        let mut data = vec![0; BOUNTY_VARIABLE_DATA_OFFSET];
+        data[..8].copy_from_slice(&Bounty::DISCRIMINATOR);
+        data[8] = Bounty::VERSION;
+        header.pack(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn header_fields_sit_at_their_offsets() {
+        let header = header();
+        let data = packed(&header);
+        let bytes = |offset: usize, len: usize| &data[offset..offset + len];
+
+        assert_eq!(data[BOUNTY_STATE_OFFSET], BountyStatus::Claimed as u8);
+        assert_eq!(bytes(BOUNTY_CREATOR_OFFSET, 32), [1; 32]);
+        assert_eq!(bytes(BOUNTY_TOKEN_MINT_OFFSET, 32), [2; 32]);
+        assert_eq!(bytes(BOUNTY_CLAIMANT_OFFSET, 32), [3; 32]);
+        assert_eq!(bytes(BOUNTY_DEADLINE_OFFSET, 8), 1_700_000_004i64.to_le_bytes());
+        assert_eq!(bytes(BOUNTY_REPOSITORY_HASH_OFFSET, 32), [5; 32]);
+        assert_eq!(bytes(BOUNTY_ISSUE_HASH_OFFSET, 32), [6; 32]);
+        assert_eq!(bytes(BOUNTY_AMOUNT_OFFSET, 8), 7_000_000u64.to_le_bytes());
+        assert_eq!(bytes(BOUNTY_CREATED_AT_OFFSET, 8), 1_700_000_008i64.to_le_bytes());
+        assert_eq!(bytes(BOUNTY_LOCKED_AT_OFFSET, 8), 1_700_000_009i64.to_le_bytes());
+        assert_eq!(bytes(BOUNTY_CLAIMED_AT_OFFSET, 8), 1_700_000_010i64.to_le_bytes());
+        assert_eq!(bytes(BOUNTY_COMPLETED_AT_OFFSET, 8), 1_700_000_011i64.to_le_bytes());
+        assert_eq!(bytes(BOUNTY_FEE_COLLECTOR_OFFSET, 32), [12; 32]);
+        assert_eq!(data[BOUNTY_FEE_PERCENTAGE_OFFSET], 13);
+        assert_eq!(data[BOUNTY_BUMP_OFFSET], 14);
+        assert_eq!(data[BOUNTY_VAULT_BUMP_OFFSET], 15);
+        assert_eq!(data[BOUNTY_PLATFORM_OFFSET], ForgePlatform::Gitea as u8);
+        assert_eq!(bytes(BOUNTY_SEED_CREATOR_OFFSET, 32), [17; 32]);
+        assert_eq!(bytes(BOUNTY_PENDING_CREATOR_OFFSET, 32), [18; 32]);
+        assert_eq!(data[BOUNTY_UNFROZEN_STATE_OFFSET], BountyStatus::Locked as u8);
+        assert_eq!(data[BOUNTY_MODERATION_REASON_OFFSET], 20);
+        assert_eq!(data[BOUNTY_LEGACY_ADDRESS_OFFSET], 1);
+
+        // The fixed fields fill the space up to the variable-length data exactly
+        assert_eq!(ACCOUNT_HEADER_LEN + borsh::to_vec(&header).unwrap().len(), BOUNTY_VARIABLE_DATA_OFFSET);
+        assert_eq!(BountyHeader::unpack(&data).unwrap(), header);
+    }
+
+    #[test]
+    fn unset_optional_fields_are_zeroed() {
+        let mut header = header();
+        header.token_mint = None;
+        header.claimant = None;
+        header.locked_at = None;
+        header.fee_collector = None;
+        header.pending_creator = None;
+        let data = packed(&header);
+
+        assert_eq!(data[BOUNTY_TOKEN_MINT_OFFSET..BOUNTY_TOKEN_MINT_OFFSET + 32], [0; 32]);
+        assert_eq!(data[BOUNTY_CLAIMANT_OFFSET..BOUNTY_CLAIMANT_OFFSET + 32], [0; 32]);
+        assert_eq!(data[BOUNTY_LOCKED_AT_OFFSET..BOUNTY_LOCKED_AT_OFFSET + 8], [0; 8]);
+        assert_eq!(data[BOUNTY_FEE_COLLECTOR_OFFSET..BOUNTY_FEE_COLLECTOR_OFFSET + 32], [0; 32]);
+        assert_eq!(data[BOUNTY_PENDING_CREATOR_OFFSET..BOUNTY_PENDING_CREATOR_OFFSET + 32], [0; 32]);
+        assert_eq!(BountyHeader::unpack(&data).unwrap(), header);
+    }
+
+    fn header_in(state: BountyStatus) -> BountyHeader {
+        BountyHeader {
+            state,
+            unfrozen_state: BountyStatus::Available,
+            moderation_reason: 0,
+            ..header()
+        }
+    }
+
+    #[test]
+    fn freeze_and_unfreeze_restore_the_previous_state() {
+        for state in [BountyStatus::Available, BountyStatus::Locked, BountyStatus::Claimed] {
+            let mut bounty = header_in(state);
+
+            bounty.freeze().unwrap();
+            assert_eq!(bounty.state, BountyStatus::Frozen);
+            assert_eq!(bounty.unfrozen_state, state);
+
+            bounty.unfreeze().unwrap();
+            assert_eq!(bounty.state, state);
+            assert_eq!(bounty.unfrozen_state, BountyStatus::Available);
+            assert_eq!(bounty.moderation_reason, 0);
+        }
+    }
+
+    #[test]
+    fn freeze_rejects_terminal_and_frozen_bounties() {
+        for state in [BountyStatus::Completed, BountyStatus::Cancelled, BountyStatus::Frozen] {
+            let mut bounty = header_in(state);
+            assert_eq!(bounty.freeze(), Err(BountyError::InvalidBountyState.into()));
+            assert_eq!(bounty.state, state);
+        }
+
+        // A second freeze must not overwrite the state to return to
+        let mut bounty = header_in(BountyStatus::Locked);
+        bounty.freeze().unwrap();
+        assert!(bounty.freeze().is_err());
+        assert_eq!(bounty.unfrozen_state, BountyStatus::Locked);
+    }
+
+    #[test]
+    fn unfreeze_rejects_bounties_that_are_not_frozen() {
+        for state in [
+            BountyStatus::Available,
+            BountyStatus::Locked,
+            BountyStatus::Claimed,
+            BountyStatus::Completed,
+            BountyStatus::Cancelled,
+        ] {
+            let mut bounty = header_in(state);
+            assert_eq!(bounty.unfreeze(), Err(BountyError::InvalidBountyState.into()));
+            assert_eq!(bounty.state, state);
+        }
+    }
+
+    #[test]
+    fn force_cancel_records_the_reason() {
+        for state in [BountyStatus::Available, BountyStatus::Locked, BountyStatus::Claimed] {
+            let mut bounty = header_in(state);
+            bounty.force_cancel(3).unwrap();
+            assert_eq!(bounty.state, BountyStatus::Cancelled);
+            assert_eq!(bounty.moderation_reason, 3);
+        }
+
+        // Cancelling a frozen bounty also clears the state it would have returned to
+        let mut bounty = header_in(BountyStatus::Claimed);
+        bounty.freeze().unwrap();
+        bounty.force_cancel(7).unwrap();
+        assert_eq!(bounty.state, BountyStatus::Cancelled);
+        assert_eq!(bounty.unfrozen_state, BountyStatus::Available);
+        assert_eq!(bounty.moderation_reason, 7);
+        assert!(bounty.unfreeze().is_err());
+    }
+
+    #[test]
+    fn force_cancel_rejects_terminal_bounties_and_a_zero_reason() {
+        for state in [BountyStatus::Completed, BountyStatus::Cancelled] {
+            let mut bounty = header_in(state);
+            assert_eq!(bounty.force_cancel(3), Err(BountyError::InvalidBountyState.into()));
+            assert_eq!(bounty.state, state);
+            assert_eq!(bounty.moderation_reason, 0);
+        }
+
+        let mut bounty = header_in(BountyStatus::Available);
+        assert_eq!(bounty.force_cancel(0), Err(ProgramError::InvalidArgument));
+        assert_eq!(bounty.state, BountyStatus::Available);
+    }
+}
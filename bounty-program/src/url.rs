@@ -0,0 +1,394 @@
+use crate::{error::BountyError, state::ForgePlatform};
+
+/// Maximum length of a repository, issue or pull request URL
+pub const MAX_URL_LEN: usize = 256;
+
+/// Maximum length of an owner, group or repository name
+pub const MAX_NAME_LEN: usize = 100;
+
+/// Maximum length of a host name
+pub const MAX_HOST_LEN: usize = 253;
+
+/// Hosts of the public forge instances
+///
+/// URLs on these hosts must be for the matching platform. GitLab and Gitea
+/// can also be self-hosted, so the parser accepts any other valid host name
+/// for them, and the program only takes bounties on those hosts once an
+/// admin has allowed them, see `AllowedHost`.
+pub const PUBLIC_HOSTS: &[(ForgePlatform, &str)] = &[
+    (ForgePlatform::GitHub, "github.com"),
+    (ForgePlatform::GitLab, "gitlab.com"),
+    (ForgePlatform::Bitbucket, "bitbucket.org"),
+];
+
+/// What a forge URL points to
+///
+/// GitLab merge requests are pull requests here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeUrlKind {
+    Repository,
+    Issue(u64),
+    PullRequest(u64),
+}
+
+/// A parsed repository, issue or pull request URL
+///
+/// Accepted shapes, with an optional trailing `/`:
+///
+/// - GitHub: `https://<host>/<owner>/<repo>`, `.../issues/<n>` and `.../pull/<n>`
+/// - GitLab: `https://<host>/<group>[/<subgroup>...]/<repo>`, `.../-/issues/<n>`
+///   and `.../-/merge_requests/<n>`
+/// - Gitea: `https://<host>/<owner>/<repo>`, `.../issues/<n>` and `.../pulls/<n>`
+/// - Bitbucket: `https://<host>/<workspace>/<repo>`, `.../issues/<n>` and
+///   `.../pull-requests/<n>`
+///
+/// Queries and fragments are rejected. Parsing only borrows from the input
+/// and uses nothing from `std`, so clients can run the exact checks the
+/// program does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForgeUrl<'a> {
+    pub platform: ForgePlatform,
+    pub host: &'a str,
+    /// Owner of the repository, `/`-separated groups for nested GitLab groups
+    pub owner: &'a str,
+    pub repo: &'a str,
+    pub kind: ForgeUrlKind,
+}
+
+impl<'a> ForgeUrl<'a> {
+    /// Parse any supported URL for `platform`
+    pub fn parse(platform: ForgePlatform, url: &'a str) -> Result<Self, BountyError> {
+        if url.len() > MAX_URL_LEN {
+            return Err(BountyError::UrlTooLong);
+        }
+
+        let rest = strip_prefix_ignore_case(url, "https://").ok_or(BountyError::InvalidUrlScheme)?;
+        let (host, path) = rest.split_once('/').ok_or(BountyError::MalformedUrl)?;
+        check_host(platform, host)?;
+
+        let path = path.strip_suffix('/').unwrap_or(path);
+
+        // Split the repository path from the issue or pull request part
+        let (project, item) = match platform {
+            ForgePlatform::GitLab => match path.split_once("/-/") {
+                Some((project, item)) => (project, Some(item)),
+                None => (path, None),
+            },
+            _ => match path.match_indices('/').nth(1) {
+                Some((index, _)) => (&path[..index], Some(&path[index + 1..])),
+                None => (path, None),
+            },
+        };
+
+        let (owner, repo) = project.rsplit_once('/').ok_or(BountyError::MalformedUrl)?;
+
+        let kind = match item {
+            None => ForgeUrlKind::Repository,
+            Some(item) => {
+                let (segment, number) = item.split_once('/').ok_or(BountyError::MalformedUrl)?;
+                let number = parse_number(number)?;
+
+                if segment == "issues" {
+                    ForgeUrlKind::Issue(number)
+                } else if segment == pull_request_segment(platform) {
+                    ForgeUrlKind::PullRequest(number)
+                } else {
+                    return Err(BountyError::MalformedUrl);
+                }
+            }
+        };
+
+        // Clone URLs of a repository point to the same repository
+        let repo = match kind {
+            ForgeUrlKind::Repository => repo.strip_suffix(".git").unwrap_or(repo),
+            _ => repo,
+        };
+
+        for name in owner.split('/') {
+            check_name(name)?;
+        }
+        check_name(repo)?;
+
+        Ok(Self { platform, host, owner, repo, kind })
+    }
+
+    /// Parse a repository URL
+    pub fn parse_repository(platform: ForgePlatform, url: &'a str) -> Result<Self, BountyError> {
+        let parsed = Self::parse(platform, url)?;
+        match parsed.kind {
+            ForgeUrlKind::Repository => Ok(parsed),
+            _ => Err(BountyError::InvalidRepositoryUrl),
+        }
+    }
+
+    /// Parse an issue URL
+    pub fn parse_issue(platform: ForgePlatform, url: &'a str) -> Result<Self, BountyError> {
+        let parsed = Self::parse(platform, url)?;
+        match parsed.kind {
+            ForgeUrlKind::Issue(_) => Ok(parsed),
+            _ => Err(BountyError::InvalidIssueUrl),
+        }
+    }
+
+    /// Parse a pull request URL, or a merge request URL on GitLab
+    pub fn parse_pull_request(platform: ForgePlatform, url: &'a str) -> Result<Self, BountyError> {
+        let parsed = Self::parse(platform, url)?;
+        match parsed.kind {
+            ForgeUrlKind::PullRequest(_) => Ok(parsed),
+            _ => Err(BountyError::InvalidPullRequestUrl),
+        }
+    }
+
+    /// Issue or pull request number, `None` for repository URLs
+    pub fn number(&self) -> Option<u64> {
+        match self.kind {
+            ForgeUrlKind::Repository => None,
+            ForgeUrlKind::Issue(number) | ForgeUrlKind::PullRequest(number) => Some(number),
+        }
+    }
+
+    /// Whether both URLs belong to the same repository
+    ///
+    /// Hosts, owners and repository names are compared case-insensitively.
+    pub fn same_repository(&self, other: &ForgeUrl) -> bool {
+        self.platform == other.platform
+            && self.host.eq_ignore_ascii_case(other.host)
+            && self.owner.eq_ignore_ascii_case(other.owner)
+            && self.repo.eq_ignore_ascii_case(other.repo)
+    }
+
+    /// Whether the URL is on a self-hosted instance rather than one of `PUBLIC_HOSTS`
+    pub fn is_self_hosted(&self) -> bool {
+        !is_public_host(self.host)
+    }
+}
+
+/// Checks a host an admin wants to allow bounties on
+///
+/// Only GitLab and Gitea can be self-hosted, and public hosts need no entry.
+pub fn check_self_hosted_host(platform: ForgePlatform, host: &str) -> Result<(), BountyError> {
+    if is_public_host(host) {
+        return Err(BountyError::UrlHostNotAllowed);
+    }
+    check_host(platform, host)
+}
+
+fn is_public_host(host: &str) -> bool {
+    PUBLIC_HOSTS.iter().any(|(_, public_host)| host.eq_ignore_ascii_case(public_host))
+}
+
+/// Path segment that precedes pull request numbers on each platform
+fn pull_request_segment(platform: ForgePlatform) -> &'static str {
+    match platform {
+        ForgePlatform::GitHub => "pull",
+        ForgePlatform::GitLab => "merge_requests",
+        ForgePlatform::Gitea => "pulls",
+        ForgePlatform::Bitbucket => "pull-requests",
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = value.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        Some(&value[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// Public hosts only serve their own platform, self-hostable platforms accept other valid host names
+fn check_host(platform: ForgePlatform, host: &str) -> Result<(), BountyError> {
+    if let Some((host_platform, _)) = PUBLIC_HOSTS
+        .iter()
+        .find(|(_, public_host)| host.eq_ignore_ascii_case(public_host))
+    {
+        return if *host_platform == platform {
+            Ok(())
+        } else {
+            Err(BountyError::UrlHostNotAllowed)
+        };
+    }
+
+    match platform {
+        ForgePlatform::GitLab | ForgePlatform::Gitea => check_host_name(host),
+        ForgePlatform::GitHub | ForgePlatform::Bitbucket => Err(BountyError::UrlHostNotAllowed),
+    }
+}
+
+/// DNS host names: dot-separated labels of ASCII letters, digits and inner `-`
+fn check_host_name(host: &str) -> Result<(), BountyError> {
+    if host.len() > MAX_HOST_LEN {
+        return Err(BountyError::UrlTooLong);
+    }
+
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+    };
+
+    if !host.contains('.') || !host.split('.').all(valid_label) {
+        return Err(BountyError::UrlHostNotAllowed);
+    }
+
+    Ok(())
+}
+
+/// Owner and repository names: ASCII letters, digits, `-`, `_` and `.`
+fn check_name(name: &str) -> Result<(), BountyError> {
+    if name.len() > MAX_NAME_LEN {
+        return Err(BountyError::UrlTooLong);
+    }
+
+    let valid_chars = name
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.'));
+
+    if name.is_empty() || name == "." || name == ".." || !valid_chars {
+        return Err(BountyError::MalformedUrl);
+    }
+
+    Ok(())
+}
+
+/// Issue and pull request numbers: decimal, positive, without leading zeros
+fn parse_number(number: &str) -> Result<u64, BountyError> {
+    if number.is_empty() || number.starts_with('0') || !number.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(BountyError::MalformedUrl);
+    }
+
+    number.parse().map_err(|_| BountyError::MalformedUrl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(platform: ForgePlatform, url: &str) -> Result<(&str, &str, &str, ForgeUrlKind), BountyError> {
+        ForgeUrl::parse(platform, url).map(|url| (url.host, url.owner, url.repo, url.kind))
+    }
+
+    #[test]
+    fn parses_every_url_shape() {
+        use ForgeUrlKind::*;
+
+        let cases = [
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties", Repository),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/1", Issue(1)),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/pull/2", PullRequest(2)),
+            (ForgePlatform::GitLab, "https://gitlab.com/solforge/bounties", Repository),
+            (ForgePlatform::GitLab, "https://gitlab.com/solforge/bounties/-/issues/3", Issue(3)),
+            (ForgePlatform::GitLab, "https://gitlab.com/solforge/bounties/-/merge_requests/4", PullRequest(4)),
+            (ForgePlatform::Gitea, "https://gitea.example.org/solforge/bounties", Repository),
+            (ForgePlatform::Gitea, "https://gitea.example.org/solforge/bounties/issues/5", Issue(5)),
+            (ForgePlatform::Gitea, "https://gitea.example.org/solforge/bounties/pulls/6", PullRequest(6)),
+            (ForgePlatform::Bitbucket, "https://bitbucket.org/solforge/bounties", Repository),
+            (ForgePlatform::Bitbucket, "https://bitbucket.org/solforge/bounties/issues/7", Issue(7)),
+            (ForgePlatform::Bitbucket, "https://bitbucket.org/solforge/bounties/pull-requests/8", PullRequest(8)),
+        ];
+
+        for (platform, url, kind) in cases {
+            let parsed = ForgeUrl::parse(platform, url).unwrap();
+            assert_eq!((parsed.owner, parsed.repo, parsed.kind), ("solforge", "bounties", kind), "{}", url);
+        }
+    }
+
+    #[test]
+    fn parses_nested_gitlab_groups_and_clone_urls() {
+        assert_eq!(
+            parse(ForgePlatform::GitLab, "https://gitlab.com/solforge/tools/bounties/-/issues/1"),
+            Ok(("gitlab.com", "solforge/tools", "bounties", ForgeUrlKind::Issue(1)))
+        );
+        assert_eq!(
+            parse(ForgePlatform::GitHub, "https://github.com/solforge/bounties.git"),
+            Ok(("github.com", "solforge", "bounties", ForgeUrlKind::Repository))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_urls() {
+        let cases = [
+            (ForgePlatform::GitHub, "http://github.com/solforge/bounties", BountyError::InvalidUrlScheme),
+            (ForgePlatform::GitHub, "ftp://github.com/solforge/bounties", BountyError::InvalidUrlScheme),
+            (ForgePlatform::GitHub, "https://github.co/solforge/bounties", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::GitHub, "https://gitlab.com/solforge/bounties", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::Bitbucket, "https://git.example.org/solforge/bounties", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::GitLab, "https://github.com/solforge/bounties", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::GitLab, "https://-git.example.org/solforge/bounties", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::GitLab, "https://git.example.org:8443/solforge/bounties", BountyError::UrlHostNotAllowed),
+            (ForgePlatform::GitHub, "https://github.com", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/01", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/pulls/1", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/pull/1/files", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties/issues/1?x=1", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/../issues/1", BountyError::MalformedUrl),
+            (ForgePlatform::GitHub, "https://github.com/solforge/bounties//", BountyError::MalformedUrl),
+        ];
+
+        for (platform, url, error) in cases {
+            assert_eq!(parse(platform, url), Err(error), "{}", url);
+        }
+
+        let long_url = format!("https://github.com/solforge/{}", "a".repeat(MAX_URL_LEN));
+        assert_eq!(parse(ForgePlatform::GitHub, &long_url), Err(BountyError::UrlTooLong));
+    }
+
+    #[test]
+    fn checks_the_expected_kind() {
+        let repository = "https://github.com/solforge/bounties";
+        let issue = "https://github.com/solforge/bounties/issues/1";
+        let pull_request = "https://github.com/solforge/bounties/pull/1";
+
+        assert!(ForgeUrl::parse_repository(ForgePlatform::GitHub, repository).is_ok());
+        assert_eq!(
+            ForgeUrl::parse_repository(ForgePlatform::GitHub, issue),
+            Err(BountyError::InvalidRepositoryUrl)
+        );
+        assert_eq!(ForgeUrl::parse_issue(ForgePlatform::GitHub, pull_request), Err(BountyError::InvalidIssueUrl));
+        assert_eq!(
+            ForgeUrl::parse_pull_request(ForgePlatform::GitHub, issue),
+            Err(BountyError::InvalidPullRequestUrl)
+        );
+    }
+
+    #[test]
+    fn compares_repositories_ignoring_case_and_trailing_slash() {
+        let repository = ForgeUrl::parse(ForgePlatform::GitHub, "https://github.com/solforge/bounties").unwrap();
+
+        for url in [
+            "HTTPS://GitHub.com/SolForge/Bounties/",
+            "https://github.com/solforge/bounties.git",
+            "https://github.com/solforge/bounties/issues/1/",
+            "https://github.com/SOLFORGE/bounties/pull/2",
+        ] {
+            let other = ForgeUrl::parse(ForgePlatform::GitHub, url).unwrap();
+            assert!(repository.same_repository(&other), "{}", url);
+        }
+
+        let fork = ForgeUrl::parse(ForgePlatform::GitHub, "https://github.com/attacker/bounties/pull/2").unwrap();
+        assert!(!repository.same_repository(&fork));
+    }
+
+    #[test]
+    fn tells_self_hosted_hosts_apart() {
+        let public = ForgeUrl::parse(ForgePlatform::GitLab, "https://GitLab.com/solforge/bounties").unwrap();
+        let self_hosted = ForgeUrl::parse(ForgePlatform::GitLab, "https://git.example.org/solforge/bounties").unwrap();
+        assert!(!public.is_self_hosted());
+        assert!(self_hosted.is_self_hosted());
+
+        assert_eq!(check_self_hosted_host(ForgePlatform::Gitea, "git.example.org"), Ok(()));
+        assert_eq!(
+            check_self_hosted_host(ForgePlatform::GitLab, "gitlab.com"),
+            Err(BountyError::UrlHostNotAllowed)
+        );
+        assert_eq!(
+            check_self_hosted_host(ForgePlatform::GitHub, "git.example.org"),
+            Err(BountyError::UrlHostNotAllowed)
+        );
+    }
+}
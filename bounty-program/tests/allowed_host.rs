@@ -0,0 +1,100 @@
+mod common;
+
+use bounty_program::{
+    error::BountyError,
+    instruction::{find_bounty_address, BountyInstruction},
+    issue::IssueId,
+    state::{AdminAction, ForgePlatform},
+};
+use solana_program_test::{tokio, BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    clock::Clock,
+    signature::{Keypair, Signer},
+};
+
+const HOST: &str = "git.example.org";
+
+async fn create_sol_bounty(
+    context: &mut ProgramTestContext,
+    creator: &Keypair,
+    repository_url: &str,
+) -> Result<(), BanksClientError> {
+    let program_id = bounty_program::id();
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let issue_url = format!("{}/-/issues/1", repository_url);
+    let issue_hash = IssueId::from_issue_url(ForgePlatform::GitLab, &issue_url).unwrap().hash();
+    let (bounty, _) = find_bounty_address(&program_id, &issue_hash, &creator.pubkey());
+
+    let instruction = BountyInstruction::create_sol_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        1_000_000,
+        "Fix the bug".to_string(),
+        issue_hash,
+        issue_url,
+        repository_url.to_string(),
+        clock.unix_timestamp + 30 * 24 * 60 * 60,
+        ForgePlatform::GitLab,
+        None,
+    )
+    .unwrap();
+    common::send(context, &[instruction], &[creator]).await
+}
+
+#[tokio::test]
+async fn self_hosted_repositories_need_an_allowed_host() {
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let repository_url = format!("https://{}/solforge/bounties", HOST);
+    let result = create_sol_bounty(&mut context, &creator, &repository_url).await;
+    common::assert_bounty_error(result, BountyError::UrlHostNotAllowed);
+
+    let action = AdminAction::AllowHost { platform: ForgePlatform::GitLab, host: HOST.to_string() };
+    common::execute_admin_action(&mut context, &admin, 0, action).await;
+
+    // Host names are case-insensitive
+    let repository_url = format!("https://{}/solforge/bounties", HOST.to_uppercase());
+    create_sol_bounty(&mut context, &creator, &repository_url).await.unwrap();
+
+    // Public hosts need no entry
+    create_sol_bounty(&mut context, &creator, "https://gitlab.com/solforge/bounties").await.unwrap();
+}
+
+#[tokio::test]
+async fn disallowed_hosts_reject_new_bounties() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let action = AdminAction::AllowHost { platform: ForgePlatform::GitLab, host: HOST.to_string() };
+    common::execute_admin_action(&mut context, &admin, 0, action).await;
+
+    let disallow = BountyInstruction::disallow_host(&program_id, &admin.pubkey(), ForgePlatform::GitLab, HOST).unwrap();
+    common::send(&mut context, &[disallow], &[&admin]).await.unwrap();
+
+    let repository_url = format!("https://{}/solforge/bounties", HOST);
+    let result = create_sol_bounty(&mut context, &creator, &repository_url).await;
+    common::assert_bounty_error(result, BountyError::UrlHostNotAllowed);
+}
+
+#[tokio::test]
+async fn only_self_hosted_hosts_can_be_allowed() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let mut context = common::start(&admin, &[]).await;
+
+    let actions = [
+        AdminAction::AllowHost { platform: ForgePlatform::GitLab, host: "gitlab.com".to_string() },
+        AdminAction::AllowHost { platform: ForgePlatform::GitHub, host: HOST.to_string() },
+        AdminAction::AllowHost { platform: ForgePlatform::Gitea, host: "localhost".to_string() },
+    ];
+    for action in actions {
+        let propose = BountyInstruction::propose_action(&program_id, &admin.pubkey(), 0, action, None).unwrap();
+        let result = common::send(&mut context, &[propose], &[&admin]).await;
+        common::assert_bounty_error(result, BountyError::UrlHostNotAllowed);
+    }
+}
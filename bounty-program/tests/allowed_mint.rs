@@ -0,0 +1,61 @@
+mod common;
+
+use bounty_program::{
+    error::BountyError,
+    instruction::BountyInstruction,
+    state::AdminAction,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn create_rejects_mints_without_an_allowed_mint() {
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let source = common::create_token_account(&mut context, &creator, &mint, &creator.pubkey(), 1_000).await;
+
+    let result = common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 400).await;
+    common::assert_bounty_error(result.map(|_| ()), BountyError::InvalidTokenMint);
+    assert_eq!(common::token_balance(&mut context, &source).await, 1_000);
+
+    common::allow_mint(&mut context, &admin, 0, &mint).await;
+    common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 400).await.unwrap();
+}
+
+#[tokio::test]
+async fn create_rejects_disallowed_mints() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let source = common::create_token_account(&mut context, &creator, &mint, &creator.pubkey(), 1_000).await;
+    common::allow_mint(&mut context, &admin, 0, &mint).await;
+
+    let disallow = BountyInstruction::disallow_mint(&program_id, &admin.pubkey(), &mint).unwrap();
+    common::send(&mut context, &[disallow], &[&admin]).await.unwrap();
+
+    let result = common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 400).await;
+    common::assert_bounty_error(result.map(|_| ()), BountyError::InvalidTokenMint);
+}
+
+#[tokio::test]
+async fn create_enforces_the_minimum_amount() {
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let source = common::create_token_account(&mut context, &creator, &mint, &creator.pubkey(), 1_000).await;
+    let action = AdminAction::AllowMint { mint, min_amount: 500, fee_percentage: None };
+    common::execute_admin_action(&mut context, &admin, 0, action).await;
+
+    let result = common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 499).await;
+    common::assert_bounty_error(result.map(|_| ()), BountyError::InvalidAmount);
+
+    common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 500).await.unwrap();
+}
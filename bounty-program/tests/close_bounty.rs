@@ -0,0 +1,65 @@
+mod common;
+
+use bounty_program::{
+    error::BountyError,
+    instruction::{find_token_vault_address, BountyInstruction, BountyToken},
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn close_sweeps_tokens_sent_to_the_vault() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let source = common::create_token_account(&mut context, &creator, &mint, &creator.pubkey(), 1_000).await;
+    common::allow_mint(&mut context, &admin, 0, &mint).await;
+    let bounty = common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 400).await.unwrap();
+    let token = BountyToken { program: spl_token::id(), mint };
+
+    let cancel = BountyInstruction::cancel_bounty(&program_id, &creator.pubkey(), &bounty, &source, Some(&token))
+        .unwrap();
+    common::send(&mut context, &[cancel], &[&creator]).await.unwrap();
+
+    // Dust sent to the vault by anyone used to keep it from closing
+    let (vault, _) = find_token_vault_address(&program_id, &bounty);
+    let dust = spl_token::instruction::transfer(&spl_token::id(), &source, &vault, &creator.pubkey(), &[], 7).unwrap();
+    common::send(&mut context, &[dust], &[&creator]).await.unwrap();
+    assert_eq!(common::token_balance(&mut context, &source).await, 993);
+
+    let close = BountyInstruction::close_bounty(&program_id, &creator.pubkey(), &bounty, &source, Some(&token))
+        .unwrap();
+    common::send(&mut context, &[close], &[&creator]).await.unwrap();
+
+    assert_eq!(common::token_balance(&mut context, &source).await, 1_000);
+    assert!(context.banks_client.get_account(vault).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn close_rejects_a_refund_account_the_creator_does_not_own() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let source = common::create_token_account(&mut context, &creator, &mint, &creator.pubkey(), 1_000).await;
+    let other = common::create_token_account(&mut context, &creator, &mint, &admin.pubkey(), 0).await;
+    common::allow_mint(&mut context, &admin, 0, &mint).await;
+    let bounty = common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 400).await.unwrap();
+    let token = BountyToken { program: spl_token::id(), mint };
+
+    let cancel = BountyInstruction::cancel_bounty(&program_id, &creator.pubkey(), &bounty, &source, Some(&token))
+        .unwrap();
+    common::send(&mut context, &[cancel], &[&creator]).await.unwrap();
+
+    let close = BountyInstruction::close_bounty(&program_id, &creator.pubkey(), &bounty, &other, Some(&token))
+        .unwrap();
+    common::assert_bounty_error(
+        common::send(&mut context, &[close], &[&creator]).await,
+        BountyError::TokenAccountOwnerMismatch,
+    );
+}
@@ -0,0 +1,244 @@
+//! Helpers shared by the program tests, which run the processor natively
+
+#![allow(dead_code)]
+
+use bounty_program::{
+    error::BountyError,
+    instruction::{find_bounty_address, find_program_config_address, find_program_data_address, BountyInstruction},
+    issue::IssueId,
+    state::{AccountState, AdminAction, ForgePlatform, ProgramConfig},
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::Clock,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    instruction::InstructionError,
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+pub const REPOSITORY_URL: &str = "https://github.com/solforge/bounties";
+
+/// Starts the program with a config administered by `admin` and funds `wallets`
+pub async fn start(admin: &Keypair, wallets: &[&Keypair]) -> ProgramTestContext {
+    let program_id = bounty_program::id();
+    let mut program_test = program_test(admin, wallets);
+
+    let (config_address, bump) = find_program_config_address(&program_id);
+    let mut data = vec![0; ProgramConfig::LEN];
+    ProgramConfig::new(admin.pubkey(), bump).pack(&mut data).unwrap();
+    program_test.add_account(
+        config_address,
+        Account { lamports: LAMPORTS_PER_SOL, data, owner: program_id, executable: false, rent_epoch: 0 },
+    );
+
+    program_test.start_with_context().await
+}
+
+/// Starts the program as freshly deployed by `upgrade_authority`, without a config, and funds `wallets`
+pub async fn start_deployed(upgrade_authority: &Keypair, wallets: &[&Keypair]) -> ProgramTestContext {
+    let program_id = bounty_program::id();
+    let mut program_test = program_test(upgrade_authority, wallets);
+
+    let data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(upgrade_authority.pubkey()),
+    })
+    .unwrap();
+    program_test.add_account(
+        find_program_data_address(&program_id).0,
+        Account { lamports: LAMPORTS_PER_SOL, data, owner: bpf_loader_upgradeable::id(), ..Account::default() },
+    );
+
+    program_test.start_with_context().await
+}
+
+fn program_test(signer: &Keypair, wallets: &[&Keypair]) -> ProgramTest {
+    let mut program_test =
+        ProgramTest::new("bounty_program", bounty_program::id(), processor!(bounty_program::process_instruction));
+    program_test.prefer_bpf(false);
+
+    for wallet in std::iter::once(signer).chain(wallets.iter().copied()) {
+        program_test.add_account(wallet.pubkey(), Account { lamports: 10 * LAMPORTS_PER_SOL, ..Account::default() });
+    }
+
+    program_test
+}
+
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await.unwrap();
+    context.last_blockhash = blockhash;
+    let transaction =
+        Transaction::new_signed_with_payer(instructions, Some(&signers[0].pubkey()), signers, blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Asserts that the first instruction of a transaction failed with `error`
+pub fn assert_bounty_error(result: Result<(), BanksClientError>, error: BountyError) {
+    match result.map_err(|error| error.unwrap()) {
+        Err(TransactionError::InstructionError(0, InstructionError::Custom(code))) => {
+            assert_eq!(code, error as u32)
+        }
+        other => panic!("expected {:?}, got {:?}", error, other),
+    }
+}
+
+pub async fn warp(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Creates an SPL Token mint with `authority` as mint authority
+pub async fn create_mint(context: &mut ProgramTestContext, authority: &Keypair) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &authority.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::id(), &mint.pubkey(), &authority.pubkey(), None, 6)
+            .unwrap(),
+    ];
+    send(context, &instructions, &[authority, &mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Creates a token account of `mint` owned by `owner` holding `amount` tokens
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint_authority: &Keypair,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &mint_authority.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_account3(&spl_token::id(), &account.pubkey(), mint, owner).unwrap(),
+        spl_token::instruction::mint_to(&spl_token::id(), mint, &account.pubkey(), &mint_authority.pubkey(), &[], amount)
+            .unwrap(),
+    ];
+    send(context, &instructions, &[mint_authority, &account]).await.unwrap();
+    account.pubkey()
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+/// Proposes `action` and executes it once the timelock has passed
+pub async fn execute_admin_action(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    proposal_id: u64,
+    action: AdminAction,
+) {
+    let program_id = bounty_program::id();
+
+    let propose = BountyInstruction::propose_action(&program_id, &admin.pubkey(), proposal_id, action.clone(), None)
+        .unwrap();
+    send(context, &[propose], &[admin]).await.unwrap();
+
+    warp(context, ProgramConfig::ACTION_DELAY).await;
+    let execute = BountyInstruction::execute_action(&program_id, &admin.pubkey(), proposal_id, &action, None).unwrap();
+    send(context, &[execute], &[admin]).await.unwrap();
+}
+
+/// Adds `mint` to the allowlist through a timelocked proposal
+pub async fn allow_mint(context: &mut ProgramTestContext, admin: &Keypair, proposal_id: u64, mint: &Pubkey) {
+    let action = AdminAction::AllowMint { mint: *mint, min_amount: 1, fee_percentage: None };
+    execute_admin_action(context, admin, proposal_id, action).await;
+}
+
+/// Creates a token bounty for issue `issue` of the test repository, returning its address
+pub async fn create_token_bounty(
+    context: &mut ProgramTestContext,
+    creator: &Keypair,
+    source: &Pubkey,
+    mint: &Pubkey,
+    issue: u64,
+    amount: u64,
+) -> Result<Pubkey, BanksClientError> {
+    let program_id = bounty_program::id();
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let issue_url = format!("{}/issues/{}", REPOSITORY_URL, issue);
+    let issue_hash = IssueId::from_issue_url(ForgePlatform::GitHub, &issue_url).unwrap().hash();
+    let (bounty, _) = find_bounty_address(&program_id, &issue_hash, &creator.pubkey());
+
+    let instruction = BountyInstruction::create_token_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        source,
+        mint,
+        &spl_token::id(),
+        amount,
+        "Fix the bug".to_string(),
+        issue_hash,
+        issue_url,
+        REPOSITORY_URL.to_string(),
+        clock.unix_timestamp + 30 * 24 * 60 * 60,
+        ForgePlatform::GitHub,
+        None,
+    )
+    .unwrap();
+    send(context, &[instruction], &[creator]).await.map(|()| bounty)
+}
+
+/// Creates a SOL bounty for issue `issue` of the test repository, returning its address
+pub async fn create_sol_bounty(
+    context: &mut ProgramTestContext,
+    creator: &Keypair,
+    issue: u64,
+    amount: u64,
+) -> Result<Pubkey, BanksClientError> {
+    let program_id = bounty_program::id();
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let issue_url = format!("{}/issues/{}", REPOSITORY_URL, issue);
+    let issue_hash = IssueId::from_issue_url(ForgePlatform::GitHub, &issue_url).unwrap().hash();
+    let (bounty, _) = find_bounty_address(&program_id, &issue_hash, &creator.pubkey());
+
+    let instruction = BountyInstruction::create_sol_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        amount,
+        "Fix the bug".to_string(),
+        issue_hash,
+        issue_url,
+        REPOSITORY_URL.to_string(),
+        clock.unix_timestamp + 30 * 24 * 60 * 60,
+        ForgePlatform::GitHub,
+        None,
+    )
+    .unwrap();
+    send(context, &[instruction], &[creator]).await.map(|()| bounty)
+}
+
+/// Asserts that the first instruction of a transaction failed with `error`
+pub fn assert_instruction_error(result: Result<(), BanksClientError>, error: InstructionError) {
+    assert_eq!(result.unwrap_err().unwrap(), TransactionError::InstructionError(0, error));
+}
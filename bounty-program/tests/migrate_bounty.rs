@@ -0,0 +1,114 @@
+mod common;
+
+use bounty_program::{
+    instruction::{find_legacy_escrow_authority_address, find_token_vault_address, BountyInstruction, BountyToken},
+    issue::IssueId,
+    migration::BountyV0,
+    state::{AccountState, Bounty, BountyStatus, ForgePlatform},
+};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// Writes a token bounty the way the original program did: headerless, at a
+/// keypair address, with its tokens in a separate escrow token account
+async fn create_legacy_token_bounty(
+    context: &mut ProgramTestContext,
+    creator: &Keypair,
+    mint: &Pubkey,
+    amount: u64,
+) -> (Pubkey, Pubkey) {
+    let program_id = bounty_program::id();
+    let bounty = Keypair::new().pubkey();
+    let (escrow_authority, _) = find_legacy_escrow_authority_address(&program_id, &bounty);
+    let escrow = common::create_token_account(context, creator, mint, &escrow_authority, amount).await;
+
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let issue_url = format!("{}/issues/7", common::REPOSITORY_URL);
+    let legacy = BountyV0 {
+        creator: creator.pubkey(),
+        amount,
+        description: "Fix the bug".to_string(),
+        issue_hash: IssueId::from_issue_url(ForgePlatform::GitHub, &issue_url).unwrap().hash(),
+        issue_url,
+        repository_url: common::REPOSITORY_URL.to_string(),
+        deadline: clock.unix_timestamp + 30 * 24 * 60 * 60,
+        state: BountyStatus::Available,
+        token_mint: Some(*mint),
+        claimant: None,
+        pr_url: None,
+        locked_at: None,
+        claimed_at: None,
+        completed_at: None,
+        created_at: clock.unix_timestamp,
+        fee_collector: None,
+        fee_percentage: 0,
+    };
+
+    let mut data = borsh::to_vec(&legacy).unwrap();
+    data.resize(Bounty::LEN, 0);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &bounty,
+        &Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+
+    (bounty, escrow)
+}
+
+#[tokio::test]
+async fn migrates_legacy_token_bounty_into_a_vault() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let claimant = Keypair::new();
+    let mut context = common::start(&admin, &[&creator, &claimant]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let token = BountyToken { program: spl_token::id(), mint };
+    let (bounty, escrow) = create_legacy_token_bounty(&mut context, &creator, &mint, 400).await;
+
+    let migrate = BountyInstruction::migrate_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        &creator.pubkey(),
+        Some(&token),
+        Some(&escrow),
+    )
+    .unwrap();
+    common::send(&mut context, &[migrate], &[&creator]).await.unwrap();
+
+    let (vault, _) = find_token_vault_address(&program_id, &bounty);
+    assert_eq!(common::token_balance(&mut context, &vault).await, 400);
+    assert!(context.banks_client.get_account(escrow).await.unwrap().is_none());
+
+    let account = context.banks_client.get_account(bounty).await.unwrap().unwrap();
+    let migrated = Bounty::unpack(&account.data).unwrap();
+    assert!(migrated.header.legacy_address);
+    assert_eq!(migrated.header.amount, 400);
+
+    // The migrated bounty pays out from its vault like any other
+    let reward = common::create_token_account(&mut context, &creator, &mint, &claimant.pubkey(), 0).await;
+    let pr_url = format!("{}/pull/8", common::REPOSITORY_URL);
+    let lock = BountyInstruction::lock_bounty(&program_id, &claimant.pubkey(), &bounty, pr_url).unwrap();
+    let claim = BountyInstruction::claim_bounty(&program_id, &claimant.pubkey(), &bounty).unwrap();
+    common::send(&mut context, &[lock, claim], &[&claimant]).await.unwrap();
+
+    let complete =
+        BountyInstruction::complete_bounty(&program_id, &creator.pubkey(), &bounty, &reward, Some(&token)).unwrap();
+    common::send(&mut context, &[complete], &[&creator]).await.unwrap();
+    assert_eq!(common::token_balance(&mut context, &reward).await, 400);
+    assert_eq!(common::token_balance(&mut context, &vault).await, 0);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use bounty_program::instruction::BountyInstruction;
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::InstructionError,
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn complete_pays_only_the_claimant() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let claimant = Keypair::new();
+    let mut context = common::start(&admin, &[&creator, &claimant]).await;
+    let bounty = common::create_sol_bounty(&mut context, &creator, 1, LAMPORTS_PER_SOL).await.unwrap();
+
+    let pr_url = format!("{}/pull/2", common::REPOSITORY_URL);
+    let lock = BountyInstruction::lock_bounty(&program_id, &claimant.pubkey(), &bounty, pr_url).unwrap();
+    let claim = BountyInstruction::claim_bounty(&program_id, &claimant.pubkey(), &bounty).unwrap();
+    common::send(&mut context, &[lock, claim], &[&claimant]).await.unwrap();
+
+    // The creator can't keep the reward of a claimed bounty
+    let complete =
+        BountyInstruction::complete_bounty(&program_id, &creator.pubkey(), &bounty, &creator.pubkey(), None).unwrap();
+    let result = common::send(&mut context, &[complete], &[&creator]).await;
+    common::assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let claimant_before = context.banks_client.get_balance(claimant.pubkey()).await.unwrap();
+    let complete =
+        BountyInstruction::complete_bounty(&program_id, &creator.pubkey(), &bounty, &claimant.pubkey(), None).unwrap();
+    common::send(&mut context, &[complete], &[&creator]).await.unwrap();
+    let claimant_after = context.banks_client.get_balance(claimant.pubkey()).await.unwrap();
+    assert_eq!(claimant_after - claimant_before, LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn refunds_go_only_to_the_creator() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let other = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+    let bounty = common::create_sol_bounty(&mut context, &creator, 1, LAMPORTS_PER_SOL).await.unwrap();
+
+    let reduce = BountyInstruction::reduce_bounty(&program_id, &creator.pubkey(), &bounty, &other.pubkey(), None, 1)
+        .unwrap();
+    let result = common::send(&mut context, &[reduce], &[&creator]).await;
+    common::assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let cancel =
+        BountyInstruction::cancel_bounty(&program_id, &creator.pubkey(), &bounty, &other.pubkey(), None).unwrap();
+    let result = common::send(&mut context, &[cancel], &[&creator]).await;
+    common::assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let cancel =
+        BountyInstruction::cancel_bounty(&program_id, &creator.pubkey(), &bounty, &creator.pubkey(), None).unwrap();
+    common::send(&mut context, &[cancel], &[&creator]).await.unwrap();
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use bounty_program::{
+    error::BountyError,
+    instruction::{find_program_config_address, BountyInstruction},
+    state::{AccountState, ProgramConfig},
+};
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn only_the_upgrade_authority_initializes_the_config() {
+    let program_id = bounty_program::id();
+    let upgrade_authority = Keypair::new();
+    let admin = Keypair::new();
+    let mut context = common::start_deployed(&upgrade_authority, &[&admin]).await;
+
+    // Nothing needing an admin works before the config exists
+    let pause = BountyInstruction::set_program_config(&program_id, &admin.pubkey(), true, 0).unwrap();
+    let result = common::send(&mut context, std::slice::from_ref(&pause), &[&admin]).await;
+    common::assert_instruction_error(result, InstructionError::UninitializedAccount);
+
+    let initialize =
+        BountyInstruction::initialize_program_config(&program_id, &admin.pubkey(), &admin.pubkey(), None).unwrap();
+    let result = common::send(&mut context, &[initialize], &[&admin]).await;
+    common::assert_bounty_error(result, BountyError::Unauthorized);
+
+    let initialize = BountyInstruction::initialize_program_config(
+        &program_id,
+        &upgrade_authority.pubkey(),
+        &admin.pubkey(),
+        None,
+    )
+    .unwrap();
+    common::send(&mut context, std::slice::from_ref(&initialize), &[&upgrade_authority]).await.unwrap();
+
+    let (config_address, _) = find_program_config_address(&program_id);
+    let account = context.banks_client.get_account(config_address).await.unwrap().unwrap();
+    assert_eq!(ProgramConfig::unpack(&account.data).unwrap().admin, admin.pubkey());
+
+    // The config can only be created once
+    let result = common::send(&mut context, &[initialize], &[&upgrade_authority]).await;
+    common::assert_instruction_error(result, InstructionError::AccountAlreadyInitialized);
+
+    common::send(&mut context, &[pause], &[&admin]).await.unwrap();
+    let account = context.banks_client.get_account(config_address).await.unwrap().unwrap();
+    assert!(ProgramConfig::unpack(&account.data).unwrap().paused);
+}
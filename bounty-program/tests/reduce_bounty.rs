@@ -0,0 +1,38 @@
+mod common;
+
+use bounty_program::{error::BountyError, instruction::BountyInstruction, state::{AccountState, Bounty}};
+use solana_program_test::tokio;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+};
+
+#[tokio::test]
+async fn reduce_rejects_amounts_beyond_the_escrow() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&creator]).await;
+    let bounty = common::create_sol_bounty(&mut context, &creator, 1, LAMPORTS_PER_SOL).await.unwrap();
+
+    for amount in [LAMPORTS_PER_SOL + 1, LAMPORTS_PER_SOL, 0] {
+        let reduce =
+            BountyInstruction::reduce_bounty(&program_id, &creator.pubkey(), &bounty, &creator.pubkey(), None, amount)
+                .unwrap();
+        let result = common::send(&mut context, &[reduce], &[&creator]).await;
+        common::assert_bounty_error(result, BountyError::InvalidAmount);
+    }
+
+    let reduce = BountyInstruction::reduce_bounty(
+        &program_id,
+        &creator.pubkey(),
+        &bounty,
+        &creator.pubkey(),
+        None,
+        LAMPORTS_PER_SOL / 4,
+    )
+    .unwrap();
+    common::send(&mut context, &[reduce], &[&creator]).await.unwrap();
+    let account = context.banks_client.get_account(bounty).await.unwrap().unwrap();
+    assert_eq!(Bounty::unpack(&account.data).unwrap().header.amount, LAMPORTS_PER_SOL * 3 / 4);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use bounty_program::{
+    error::BountyError,
+    instruction::BountyInstruction,
+    state::{AccountState, Bounty, BountyStatus, Role},
+};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+/// Creates a SOL bounty for issue `issue` and has `claimant` lock and claim it
+async fn create_claimed_bounty(
+    context: &mut ProgramTestContext,
+    creator: &Keypair,
+    claimant: &Keypair,
+    issue: u64,
+) -> Pubkey {
+    let program_id = bounty_program::id();
+    let bounty = common::create_sol_bounty(context, creator, issue, LAMPORTS_PER_SOL).await.unwrap();
+
+    let pr_url = format!("{}/pull/{}", common::REPOSITORY_URL, issue + 1);
+    let lock = BountyInstruction::lock_bounty(&program_id, &claimant.pubkey(), &bounty, pr_url).unwrap();
+    let claim = BountyInstruction::claim_bounty(&program_id, &claimant.pubkey(), &bounty).unwrap();
+    common::send(context, &[lock, claim], &[claimant]).await.unwrap();
+    bounty
+}
+
+async fn bounty_state(context: &mut ProgramTestContext, bounty: Pubkey) -> BountyStatus {
+    let account = context.banks_client.get_account(bounty).await.unwrap().unwrap();
+    Bounty::unpack(&account.data).unwrap().header.state
+}
+
+#[tokio::test]
+async fn arbiter_settles_claimed_bounties() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let arbiter = Keypair::new();
+    let creator = Keypair::new();
+    let claimant = Keypair::new();
+    let mut context = common::start(&admin, &[&arbiter, &creator, &claimant]).await;
+    let awarded = create_claimed_bounty(&mut context, &creator, &claimant, 1).await;
+    let refunded = create_claimed_bounty(&mut context, &creator, &claimant, 3).await;
+
+    // Only arbiters settle disputes
+    let resolve =
+        BountyInstruction::resolve_dispute(&program_id, &arbiter.pubkey(), &awarded, &claimant.pubkey(), None, true)
+            .unwrap();
+    let result = common::send(&mut context, std::slice::from_ref(&resolve), &[&arbiter]).await;
+    common::assert_bounty_error(result, BountyError::Unauthorized);
+
+    let grant = BountyInstruction::grant_role(&program_id, &admin.pubkey(), Role::Arbiter, &arbiter.pubkey(), None)
+        .unwrap();
+    common::send(&mut context, &[grant], &[&admin]).await.unwrap();
+
+    // An award can only be paid to the claimant
+    let misdirected =
+        BountyInstruction::resolve_dispute(&program_id, &arbiter.pubkey(), &awarded, &creator.pubkey(), None, true)
+            .unwrap();
+    let result = common::send(&mut context, &[misdirected], &[&arbiter]).await;
+    common::assert_instruction_error(result, InstructionError::InvalidArgument);
+
+    let claimant_before = context.banks_client.get_balance(claimant.pubkey()).await.unwrap();
+    common::send(&mut context, &[resolve], &[&arbiter]).await.unwrap();
+    let claimant_after = context.banks_client.get_balance(claimant.pubkey()).await.unwrap();
+    assert_eq!(claimant_after - claimant_before, LAMPORTS_PER_SOL);
+    assert_eq!(bounty_state(&mut context, awarded).await, BountyStatus::Completed);
+
+    let creator_before = context.banks_client.get_balance(creator.pubkey()).await.unwrap();
+    let refund =
+        BountyInstruction::resolve_dispute(&program_id, &arbiter.pubkey(), &refunded, &creator.pubkey(), None, false)
+            .unwrap();
+    common::send(&mut context, std::slice::from_ref(&refund), &[&arbiter]).await.unwrap();
+    let creator_after = context.banks_client.get_balance(creator.pubkey()).await.unwrap();
+    assert_eq!(creator_after - creator_before, LAMPORTS_PER_SOL);
+    assert_eq!(bounty_state(&mut context, refunded).await, BountyStatus::Cancelled);
+
+    // Settled bounties can't be settled again
+    let result = common::send(&mut context, &[refund], &[&arbiter]).await;
+    common::assert_bounty_error(result, BountyError::InvalidBountyState);
+}
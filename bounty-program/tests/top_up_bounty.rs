@@ -0,0 +1,40 @@
+mod common;
+
+use bounty_program::{
+    error::BountyError,
+    instruction::{BountyInstruction, BountyToken},
+    state::Role,
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn top_up_rejects_frozen_bounties() {
+    let program_id = bounty_program::id();
+    let admin = Keypair::new();
+    let moderator = Keypair::new();
+    let creator = Keypair::new();
+    let mut context = common::start(&admin, &[&moderator, &creator]).await;
+
+    let mint = common::create_mint(&mut context, &creator).await;
+    let source = common::create_token_account(&mut context, &creator, &mint, &creator.pubkey(), 1_000).await;
+    common::allow_mint(&mut context, &admin, 0, &mint).await;
+    let bounty = common::create_token_bounty(&mut context, &creator, &source, &mint, 1, 400).await.unwrap();
+    let token = BountyToken { program: spl_token::id(), mint };
+
+    let grant = BountyInstruction::grant_role(&program_id, &admin.pubkey(), Role::Moderator, &moderator.pubkey(), None)
+        .unwrap();
+    common::send(&mut context, &[grant], &[&admin]).await.unwrap();
+    let freeze = BountyInstruction::freeze_bounty(&program_id, &moderator.pubkey(), &bounty).unwrap();
+    common::send(&mut context, &[freeze], &[&moderator]).await.unwrap();
+
+    let top_up =
+        BountyInstruction::top_up_bounty(&program_id, &creator.pubkey(), &bounty, &source, Some(&token), 100).unwrap();
+    let result = common::send(&mut context, std::slice::from_ref(&top_up), &[&creator]).await;
+    common::assert_bounty_error(result, BountyError::InvalidBountyState);
+
+    let unfreeze = BountyInstruction::unfreeze_bounty(&program_id, &moderator.pubkey(), &bounty).unwrap();
+    common::send(&mut context, &[unfreeze], &[&moderator]).await.unwrap();
+    common::send(&mut context, &[top_up], &[&creator]).await.unwrap();
+    assert_eq!(common::token_balance(&mut context, &source).await, 500);
+}
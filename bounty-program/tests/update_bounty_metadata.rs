@@ -0,0 +1,89 @@
+mod common;
+
+use bounty_program::{
+    instruction::BountyInstruction,
+    state::{DelegatePermissions, ForgePlatform},
+};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+/// Creates a token bounty and lets `delegate` edit it on the creator's behalf
+async fn bounty_with_delegate(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    creator: &Keypair,
+    delegate: &Keypair,
+) -> Pubkey {
+    let program_id = bounty_program::id();
+    let mint = common::create_mint(context, creator).await;
+    let source = common::create_token_account(context, creator, &mint, &creator.pubkey(), 1_000).await;
+    common::allow_mint(context, admin, 0, &mint).await;
+    let bounty = common::create_token_bounty(context, creator, &source, &mint, 1, 400).await.unwrap();
+
+    let permissions = DelegatePermissions { can_extend: true, ..DelegatePermissions::default() };
+    let set_delegate =
+        BountyInstruction::set_bounty_delegate(&program_id, &creator.pubkey(), &delegate.pubkey(), permissions, None)
+            .unwrap();
+    common::send(context, &[set_delegate], &[creator]).await.unwrap();
+    bounty
+}
+
+fn shrink_description(creator: &Pubkey, delegate: &Pubkey, bounty: &Pubkey, rent_refund: &Pubkey) -> Instruction {
+    let program_id = bounty_program::id();
+    let update = BountyInstruction::update_bounty_metadata(
+        &program_id,
+        delegate,
+        bounty,
+        rent_refund,
+        ForgePlatform::GitHub,
+        Some("Fix".to_string()),
+        None,
+        None,
+    )
+    .unwrap();
+    BountyInstruction::with_org_authority(&program_id, update, creator)
+}
+
+async fn lamports(context: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*account).await.unwrap()
+}
+
+#[tokio::test]
+async fn shrinking_refunds_the_creator_when_a_delegate_signs() {
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let delegate = Keypair::new();
+    let mut context = common::start(&admin, &[&creator, &delegate]).await;
+    let bounty = bounty_with_delegate(&mut context, &admin, &creator, &delegate).await;
+
+    let bounty_before = lamports(&mut context, &bounty).await;
+    let creator_before = lamports(&mut context, &creator.pubkey()).await;
+
+    let update = shrink_description(&creator.pubkey(), &delegate.pubkey(), &bounty, &creator.pubkey());
+    common::send(&mut context, &[update], &[&delegate]).await.unwrap();
+
+    let freed = bounty_before - lamports(&mut context, &bounty).await;
+    assert!(freed > 0);
+    assert_eq!(lamports(&mut context, &creator.pubkey()).await, creator_before + freed);
+}
+
+#[tokio::test]
+async fn shrinking_rejects_a_rent_refund_other_than_the_creator() {
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let delegate = Keypair::new();
+    let mut context = common::start(&admin, &[&creator, &delegate]).await;
+    let bounty = bounty_with_delegate(&mut context, &admin, &creator, &delegate).await;
+
+    let update = shrink_description(&creator.pubkey(), &delegate.pubkey(), &bounty, &delegate.pubkey());
+    let result = common::send(&mut context, &[update], &[&delegate]).await;
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}